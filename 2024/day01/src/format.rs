@@ -0,0 +1,122 @@
+use crate::sort::Id;
+
+/// How the ID lists are laid out in the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Format {
+    /// Field separator, or `None` for runs of whitespace
+    pub delimiter: Option<char>,
+    /// The 0-based columns to read, at least two
+    pub columns: Vec<usize>,
+    pub skip_header: bool,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Format {
+            delimiter: None,
+            columns: vec![0, 1],
+            skip_header: false,
+        }
+    }
+}
+
+/// Parses a delimiter argument. `tab`, `\t` and `space` are accepted besides single characters.
+pub fn parse_delimiter(arg: &str) -> Result<Option<char>, String> {
+    match arg {
+        "whitespace" => Ok(None),
+        "tab" | "\\t" => Ok(Some('\t')),
+        "space" => Ok(Some(' ')),
+        _ => {
+            let mut chars = arg.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(Some(c)),
+                _ => Err(format!("Invalid delimiter: {:?}", arg)),
+            }
+        }
+    }
+}
+
+/// Parses a 1-based, comma separated column list like `1,3,4`
+pub fn parse_columns(arg: &str) -> Result<Vec<usize>, String> {
+    let columns = arg
+        .split(',')
+        .map(|c| match c.trim().parse::<usize>() {
+            Ok(c) if c > 0 => Ok(c - 1),
+            _ => Err(format!("Invalid column: {:?}", c)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if columns.len() < 2 {
+        return Err("At least two columns are needed".to_string());
+    }
+    if (1..columns.len()).any(|i| columns[..i].contains(&columns[i])) {
+        return Err(format!("Duplicate column in {:?}", arg));
+    }
+    Ok(columns)
+}
+
+impl Format {
+    fn fields<'a>(&self, line: &'a str) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+        match self.delimiter {
+            None => Box::new(line.split_ascii_whitespace()),
+            Some(delimiter) => Box::new(
+                line.split(delimiter)
+                    .map(|field| field.trim().trim_matches('"')),
+            ),
+        }
+    }
+
+    /// Parses the selected columns of a line into `row`, in the order of `columns`
+    pub fn parse_row<T: Id>(&self, line: &str, row: &mut Vec<T>) -> Result<(), String> {
+        row.clear();
+        row.resize(self.columns.len(), T::default());
+        let last = *self.columns.iter().max().unwrap();
+
+        let mut found = 0;
+        for (column, field) in self.fields(line).enumerate().take(last + 1) {
+            if let Some(i) = self.columns.iter().position(|&c| c == column) {
+                row[i] = field
+                    .parse()
+                    .map_err(|_| format!("column {}: invalid ID {:?}", column + 1, field))?;
+            }
+            found = column + 1;
+        }
+
+        if found <= last {
+            return Err(format!(
+                "expected at least {} columns, found {}",
+                last + 1,
+                found
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_parse_row() {
+    let mut row: Vec<u32> = vec![];
+    Format::default().parse_row("3   4", &mut row).unwrap();
+    assert_eq!(row, vec![3, 4]);
+
+    let csv = Format {
+        delimiter: parse_delimiter(",").unwrap(),
+        columns: parse_columns("3,1").unwrap(),
+        skip_header: true,
+    };
+    csv.parse_row("12, \"x\", \"7\", y", &mut row).unwrap();
+    assert_eq!(row, vec![7, 12]);
+    assert_eq!(
+        csv.parse_row("12,x", &mut row).unwrap_err(),
+        "expected at least 3 columns, found 2"
+    );
+    assert_eq!(
+        csv.parse_row("a,x,1", &mut row).unwrap_err(),
+        "column 1: invalid ID \"a\""
+    );
+
+    assert_eq!(parse_delimiter("tab"), Ok(Some('\t')));
+    assert!(parse_delimiter(";;").is_err());
+    assert!(parse_columns("1").is_err());
+    assert!(parse_columns("1,0").is_err());
+    assert!(parse_columns("2,2").is_err());
+}
@@ -0,0 +1,324 @@
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    time::Instant,
+};
+
+use format::Format;
+use sort::{ExternalSorter, Id};
+
+mod format;
+mod sort;
+
+const INPUT: &'static str = include_str!("input.txt");
+
+const USAGE: &str = "Usage: day01 [options] [<file>]
+
+Reads location ID lists from <file>, or the bundled input if none is given.
+Distance and similarity score are reported for every pair of columns.
+
+Options:
+  --u64                 Read IDs as u64 instead of u32
+  --chunk-size <ids>    Spill sorted chunks of this many IDs per column to
+                        temporary files (default: 50000000)
+  --delimiter <d>       Field separator, e.g. `,`, `;` or `tab` (default: whitespace)
+  --columns <list>      1-based columns to compare, e.g. `1,3,4` (default: 1,2)
+  --skip-header         Ignore the first line
+  --strict              Stop at the first malformed line instead of skipping it";
+
+struct Options {
+    wide: bool,
+    chunk_size: usize,
+    format: Format,
+    strict: bool,
+    path: Option<String>,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        wide: false,
+        chunk_size: 50_000_000,
+        format: Format::default(),
+        strict: false,
+        path: None,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--u64" => options.wide = true,
+            "--chunk-size" => {
+                options.chunk_size = value()?
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or("--chunk-size needs a positive number")?
+            }
+            "--delimiter" => options.format.delimiter = format::parse_delimiter(&value()?)?,
+            "--columns" => options.format.columns = format::parse_columns(&value()?)?,
+            "--skip-header" => options.format.skip_header = true,
+            "--strict" => options.strict = true,
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if options.path.is_none() && !arg.starts_with('-') => options.path = Some(arg),
+            _ => return Err(format!("Unexpected argument: {}\n\n{}", arg, USAGE)),
+        }
+    }
+    Ok(options)
+}
+
+/// Distance and similarity score of two sorted lists, computed in one pass over both.
+///
+/// The distance pairs up the IDs by position. The similarity needs the number of occurrences
+/// of each ID in both lists, which is known once a run of equal IDs ends. Finished runs are
+/// queued until the other list has reached the same ID, so only the runs between the two
+/// positions are kept in memory.
+#[derive(Default)]
+struct Metrics<T> {
+    distance: u128,
+    similarity: u128,
+    runs: [Option<(T, u64)>; 2],
+    finished: [VecDeque<(T, u64)>; 2],
+}
+
+impl<T: Id> Metrics<T> {
+    fn push(&mut self, left: T, right: T) {
+        self.distance += left.max(right).wide() - left.min(right).wide();
+        for (side, value) in [left, right].into_iter().enumerate() {
+            match &mut self.runs[side] {
+                Some((current, count)) if *current == value => *count += 1,
+                run => {
+                    if let Some(done) = run.replace((value, 1)) {
+                        self.finished[side].push_back(done);
+                    }
+                }
+            }
+        }
+        self.match_runs();
+    }
+
+    fn match_runs(&mut self) {
+        while let (Some(&(l, l_count)), Some(&(r, r_count))) =
+            (self.finished[0].front(), self.finished[1].front())
+        {
+            if l == r {
+                self.similarity += l.wide() * l_count as u128 * r_count as u128;
+            }
+            if l <= r {
+                self.finished[0].pop_front();
+            }
+            if r <= l {
+                self.finished[1].pop_front();
+            }
+        }
+    }
+
+    fn finish(mut self) -> Self {
+        for side in 0..2 {
+            if let Some(run) = self.runs[side].take() {
+                self.finished[side].push_back(run);
+            }
+        }
+        self.match_runs();
+        self
+    }
+}
+
+/// The metrics of one pair of columns
+struct ColumnPair<T> {
+    columns: (usize, usize),
+    metrics: Metrics<T>,
+}
+
+struct Report<T> {
+    rows: u64,
+    pairs: Vec<ColumnPair<T>>,
+    /// Line numbers and messages of the lines that couldn't be parsed
+    errors: Vec<(usize, String)>,
+}
+
+fn solve<T: Id>(
+    input: impl BufRead,
+    format: &Format,
+    chunk_size: usize,
+    strict: bool,
+) -> Result<Report<T>, String> {
+    let mut sorters: Vec<_> = format
+        .columns
+        .iter()
+        .map(|c| ExternalSorter::<T>::new(&format!("column{}", c + 1), chunk_size))
+        .collect();
+    let io_error = |e: io::Error| format!("I/O error: {}", e);
+    let mut errors = vec![];
+    let mut row = vec![];
+
+    for (i, line) in input.lines().enumerate() {
+        let line = line.map_err(io_error)?;
+        if line.trim().is_empty() || (i == 0 && format.skip_header) {
+            continue;
+        }
+        if let Err(e) = format.parse_row::<T>(&line, &mut row) {
+            if strict {
+                return Err(format!("Line {}: {}", i + 1, e));
+            }
+            errors.push((i + 1, e));
+            continue;
+        }
+        for (sorter, &id) in sorters.iter_mut().zip(row.iter()) {
+            sorter.push(id).map_err(io_error)?;
+        }
+    }
+
+    if sorters[0].spilled_runs() > 0 {
+        eprintln!(
+            "(spilled {} sorted runs per column)",
+            sorters[0].spilled_runs()
+        );
+    }
+
+    let mut pairs = vec![];
+    for a in 0..sorters.len() {
+        for b in a + 1..sorters.len() {
+            pairs.push(ColumnPair {
+                columns: (format.columns[a], format.columns[b]),
+                metrics: Metrics::default(),
+            });
+        }
+    }
+
+    // All columns are walked in lockstep, so every pair is done in the same pass
+    let mut sorted = sorters
+        .into_iter()
+        .map(|s| s.finish())
+        .collect::<io::Result<Vec<_>>>()
+        .map_err(io_error)?;
+    row.resize(sorted.len(), T::default());
+    let mut rows = 0;
+    'rows: loop {
+        for (column, ids) in sorted.iter_mut().enumerate() {
+            match ids.next() {
+                Some(id) => row[column] = id.map_err(io_error)?,
+                None => break 'rows,
+            }
+        }
+        rows += 1;
+        let mut pair = pairs.iter_mut();
+        for a in 0..row.len() {
+            for b in a + 1..row.len() {
+                pair.next().unwrap().metrics.push(row[a], row[b]);
+            }
+        }
+    }
+
+    Ok(Report {
+        rows,
+        pairs: pairs
+            .into_iter()
+            .map(|p| ColumnPair {
+                metrics: p.metrics.finish(),
+                ..p
+            })
+            .collect(),
+        errors,
+    })
+}
+
+fn run<T: Id>(options: &Options) -> Result<(), String> {
+    let start = Instant::now();
+
+    let report = match &options.path {
+        Some(path) => {
+            let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+            let input = BufReader::with_capacity(1 << 20, file);
+            solve::<T>(input, &options.format, options.chunk_size, options.strict)?
+        }
+        None => solve::<T>(
+            INPUT.as_bytes(),
+            &options.format,
+            options.chunk_size,
+            options.strict,
+        )?,
+    };
+
+    let elapsed = start.elapsed();
+
+    for (line, error) in &report.errors {
+        eprintln!("Line {}: {}", line, error);
+    }
+    if !report.errors.is_empty() {
+        eprintln!("(skipped {} malformed lines)", report.errors.len());
+    }
+
+    println!("Rows: {}", report.rows);
+    for pair in &report.pairs {
+        // Only label the answers with their columns if there is more than one pair
+        let suffix = match report.pairs.len() {
+            1 => String::new(),
+            _ => format!(" {}-{}", pair.columns.0 + 1, pair.columns.1 + 1),
+        };
+        println!("Distance{}: {}", suffix, pair.metrics.distance);
+        println!("Score{}: {}", suffix, pair.metrics.similarity);
+    }
+    println!("(took: {:?})", elapsed);
+    Ok(())
+}
+
+fn main() {
+    let result = parse_args().and_then(|options| {
+        if options.wide {
+            run::<u64>(&options)
+        } else {
+            run::<u32>(&options)
+        }
+    });
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+#[test]
+fn test_example() {
+    let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+    for chunk_size in [1, 2, 100] {
+        let report = solve::<u32>(input.as_bytes(), &Format::default(), chunk_size, true).unwrap();
+        assert_eq!(report.rows, 6);
+        assert_eq!(report.pairs[0].metrics.distance, 11);
+        assert_eq!(report.pairs[0].metrics.similarity, 31);
+    }
+
+    let wide = "10000000000 1\n1 10000000000\n".as_bytes();
+    let wide = solve::<u64>(wide, &Format::default(), 100, true).unwrap();
+    assert_eq!(wide.pairs[0].metrics.distance, 0);
+    assert_eq!(wide.pairs[0].metrics.similarity, 10000000001);
+
+    let error = solve::<u32>("1 2\n3\n".as_bytes(), &Format::default(), 100, true);
+    assert!(error.err().unwrap().starts_with("Line 2"));
+}
+
+#[test]
+fn test_columns() {
+    let input = "id;a;b;c\n1;3;4;4\n2;4;x;3\n3;2;5;3\n4;1;3;1\n";
+    let format = Format {
+        delimiter: Some(';'),
+        columns: vec![1, 2, 3],
+        skip_header: true,
+    };
+    let report = solve::<u32>(input.as_bytes(), &format, 2, false).unwrap();
+    assert_eq!(report.rows, 3);
+    assert_eq!(
+        report.errors,
+        vec![(3, "column 3: invalid ID \"x\"".to_string())]
+    );
+
+    let metrics: Vec<_> = report
+        .pairs
+        .iter()
+        .map(|p| (p.columns, p.metrics.distance, p.metrics.similarity))
+        .collect();
+    // a = [1, 2, 3], b = [3, 4, 5], c = [1, 3, 4]
+    assert_eq!(
+        metrics,
+        vec![((1, 2), 6, 3), ((1, 3), 2, 1 + 3), ((2, 3), 4, 3 + 4)]
+    );
+}
@@ -0,0 +1,223 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fmt::{Debug, Display},
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::PathBuf,
+    str::FromStr,
+};
+
+/// A location ID. Sums over IDs are done in `u128`, so they can't overflow.
+pub trait Id: Copy + Ord + Default + Debug + Display + FromStr + Into<u128> {
+    const BYTES: usize;
+
+    fn wide(self) -> u128 {
+        self.into()
+    }
+
+    /// The `i`-th byte, least significant first
+    fn byte(self, i: usize) -> usize;
+    fn write_to(self, writer: &mut impl Write) -> io::Result<()>;
+    /// Reads an ID written by `write_to`, or `None` at the end of the file
+    fn read_from(reader: &mut impl Read) -> io::Result<Option<Self>>;
+}
+
+macro_rules! impl_id {
+    ($t:ty) => {
+        impl Id for $t {
+            const BYTES: usize = std::mem::size_of::<$t>();
+
+            fn byte(self, i: usize) -> usize {
+                ((self >> (i * 8)) & 0xff) as usize
+            }
+
+            fn write_to(self, writer: &mut impl Write) -> io::Result<()> {
+                writer.write_all(&self.to_le_bytes())
+            }
+
+            fn read_from(reader: &mut impl Read) -> io::Result<Option<Self>> {
+                let mut bytes = [0; Self::BYTES];
+                match reader.read_exact(&mut bytes) {
+                    Ok(()) => Ok(Some(<$t>::from_le_bytes(bytes))),
+                    Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+                    Err(e) => Err(e),
+                }
+            }
+        }
+    };
+}
+
+impl_id!(u32);
+impl_id!(u64);
+
+/// LSD radix sort, one byte per pass. Passes where all IDs share the same byte are skipped,
+/// so small IDs in a wide type don't cost extra.
+pub fn radix_sort<T: Id>(values: &mut Vec<T>) {
+    let mut buffer = values.clone();
+    for i in 0..T::BYTES {
+        let mut counts = [0usize; 256];
+        for &v in values.iter() {
+            counts[v.byte(i)] += 1;
+        }
+        if counts.contains(&values.len()) {
+            continue;
+        }
+
+        let mut offsets = [0usize; 256];
+        for b in 1..256 {
+            offsets[b] = offsets[b - 1] + counts[b - 1];
+        }
+        for &v in values.iter() {
+            let b = v.byte(i);
+            buffer[offsets[b]] = v;
+            offsets[b] += 1;
+        }
+        std::mem::swap(values, &mut buffer);
+    }
+}
+
+/// Collects IDs and sorts them. Once more than `chunk_size` IDs are buffered, they are
+/// sorted and spilled to a temporary file, and the runs are merged at the end.
+pub struct ExternalSorter<T> {
+    chunk_size: usize,
+    buffer: Vec<T>,
+    runs: Vec<PathBuf>,
+    name: String,
+}
+
+impl<T: Id> ExternalSorter<T> {
+    /// `name` keeps the spill files of multiple sorters apart
+    pub fn new(name: &str, chunk_size: usize) -> Self {
+        ExternalSorter {
+            chunk_size,
+            buffer: Vec::with_capacity(chunk_size.min(1 << 20)),
+            runs: vec![],
+            name: name.to_string(),
+        }
+    }
+
+    pub fn push(&mut self, value: T) -> io::Result<()> {
+        self.buffer.push(value);
+        if self.buffer.len() >= self.chunk_size {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    pub fn spilled_runs(&self) -> usize {
+        self.runs.len()
+    }
+
+    fn spill(&mut self) -> io::Result<()> {
+        radix_sort(&mut self.buffer);
+        let path = std::env::temp_dir().join(format!(
+            "aoc-day01-{}-{}-{}.bin",
+            std::process::id(),
+            self.name,
+            self.runs.len()
+        ));
+        let mut writer = BufWriter::new(File::create(&path)?);
+        for &v in &self.buffer {
+            v.write_to(&mut writer)?;
+        }
+        writer.flush()?;
+        self.runs.push(path);
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Sorts the remaining IDs and returns all of them in ascending order
+    pub fn finish(mut self) -> io::Result<Sorted<T>> {
+        radix_sort(&mut self.buffer);
+        if self.runs.is_empty() {
+            return Ok(Sorted::Memory(std::mem::take(&mut self.buffer).into_iter()));
+        }
+
+        if !self.buffer.is_empty() {
+            self.spill()?;
+        }
+        let mut readers = vec![];
+        let mut heap = BinaryHeap::new();
+        for (i, path) in self.runs.iter().enumerate() {
+            let mut reader = BufReader::new(File::open(path)?);
+            if let Some(v) = T::read_from(&mut reader)? {
+                heap.push(Reverse((v, i)));
+            }
+            readers.push(reader);
+        }
+        Ok(Sorted::Merge {
+            readers,
+            heap,
+            paths: std::mem::take(&mut self.runs),
+        })
+    }
+}
+
+impl<T> Drop for ExternalSorter<T> {
+    fn drop(&mut self) {
+        for path in &self.runs {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+pub enum Sorted<T> {
+    Memory(std::vec::IntoIter<T>),
+    /// K-way merge of the spilled runs
+    Merge {
+        readers: Vec<BufReader<File>>,
+        heap: BinaryHeap<Reverse<(T, usize)>>,
+        paths: Vec<PathBuf>,
+    },
+}
+
+impl<T: Id> Iterator for Sorted<T> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Sorted::Memory(values) => values.next().map(Ok),
+            Sorted::Merge { readers, heap, .. } => {
+                let Reverse((value, run)) = heap.pop()?;
+                match T::read_from(&mut readers[run]) {
+                    Ok(Some(next)) => heap.push(Reverse((next, run))),
+                    Ok(None) => {}
+                    Err(e) => return Some(Err(e)),
+                }
+                Some(Ok(value))
+            }
+        }
+    }
+}
+
+impl<T> Drop for Sorted<T> {
+    fn drop(&mut self) {
+        if let Sorted::Merge { paths, .. } = self {
+            for path in paths {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_radix_sort() {
+    let mut values: Vec<u64> = vec![3, 1 << 40, 0, 255, 256, 7, 1 << 40, u64::MAX, 42];
+    let mut expected = values.clone();
+    expected.sort();
+    radix_sort(&mut values);
+    assert_eq!(values, expected);
+}
+
+#[test]
+fn test_external_sort() {
+    let mut sorter = ExternalSorter::<u32>::new("test", 4);
+    let values = [9, 3, 7, 1, 8, 2, 6, 4, 5, 0, 3];
+    for v in values {
+        sorter.push(v).unwrap();
+    }
+    assert_eq!(sorter.spilled_runs(), 2);
+    let sorted: Vec<u32> = sorter.finish().unwrap().map(|v| v.unwrap()).collect();
+    assert_eq!(sorted, vec![0, 1, 2, 3, 3, 4, 5, 6, 7, 8, 9]);
+}
@@ -0,0 +1,254 @@
+const INPUT: &'static str = include_str!("input.txt");
+
+const USAGE: &str = "Usage: day02b [options]
+
+Options:
+  --min-step <n>         Smallest allowed difference between levels (default: 1)
+  --max-step <n>         Largest allowed difference between levels (default: 3)
+  --monotonicity <m>     strict, non-strict or any (default: strict)
+  --removals <k>         How many levels may be removed from a report (default: 1)
+  --list                 Print each fixable report with the levels to remove";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Monotonicity {
+    /// All steps go in the same direction, flat steps are not allowed
+    Strict,
+    /// Steps never change direction, flat steps are allowed if `min_step` is 0
+    NonStrict,
+    /// Steps may go in either direction
+    Any,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rules {
+    min_step: i32,
+    max_step: i32,
+    monotonicity: Monotonicity,
+    max_removals: usize,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            min_step: 1,
+            max_step: 3,
+            monotonicity: Monotonicity::Strict,
+            max_removals: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Verdict {
+    Safe,
+    /// Safe once the levels at these indizes are removed
+    Fixable(Vec<usize>),
+    Unsafe,
+}
+
+impl Rules {
+    /// `direction` is 1 for increasing and -1 for decreasing reports, and ignored for `Any`
+    fn valid_step(&self, from: i32, to: i32, direction: i32) -> bool {
+        let step = to - from;
+        if step.abs() < self.min_step || step.abs() > self.max_step {
+            return false;
+        }
+        match self.monotonicity {
+            Monotonicity::Strict => step.signum() == direction,
+            Monotonicity::NonStrict => step.signum() != -direction,
+            Monotonicity::Any => true,
+        }
+    }
+
+    /// The fewest levels that have to be removed so all remaining steps are valid in the
+    /// given direction, if that is possible with at most `max_removals` levels.
+    ///
+    /// `removed[i]` is the fewest removals before `i` so that the levels up to `i` are valid
+    /// and `i` is kept. As at most `max_removals` levels can be skipped, only the previous
+    /// `max_removals + 1` levels can precede `i`, which makes this O(n * k).
+    fn fewest_removals(&self, report: &[i32], direction: i32) -> Option<Vec<usize>> {
+        let k = self.max_removals;
+        let mut removed: Vec<Option<usize>> = vec![None; report.len()];
+        let mut previous: Vec<Option<usize>> = vec![None; report.len()];
+
+        for i in 0..report.len() {
+            // Prefer keeping the closest predecessor on ties
+            for j in (i.saturating_sub(k + 1)..i).rev() {
+                let Some(before) = removed[j] else {
+                    continue;
+                };
+                let count = before + (i - j - 1);
+                if count <= k
+                    && removed[i].is_none_or(|c| count < c)
+                    && self.valid_step(report[j], report[i], direction)
+                {
+                    removed[i] = Some(count);
+                    previous[i] = Some(j);
+                }
+            }
+            // Or drop everything before `i`
+            if i <= k && removed[i].is_none_or(|c| i < c) {
+                removed[i] = Some(i);
+                previous[i] = None;
+            }
+        }
+
+        let (mut last, _) = (0..report.len())
+            .rev()
+            .filter_map(|i| Some((i, removed[i]? + (report.len() - 1 - i))))
+            .filter(|&(_, count)| count <= k)
+            .min_by_key(|&(i, count)| (count, report.len() - i))?;
+
+        let mut indizes: Vec<usize> = (last + 1..report.len()).collect();
+        while let Some(before) = previous[last] {
+            indizes.extend(before + 1..last);
+            last = before;
+        }
+        indizes.extend(0..last);
+        indizes.sort_unstable();
+        Some(indizes)
+    }
+
+    fn check(&self, report: &[i32]) -> Verdict {
+        if report.is_empty() {
+            return Verdict::Safe;
+        }
+        let directions: &[i32] = match self.monotonicity {
+            Monotonicity::Any => &[0],
+            _ => &[1, -1],
+        };
+        let best = directions
+            .iter()
+            .filter_map(|&d| self.fewest_removals(report, d))
+            .min_by_key(|removed| removed.len());
+        match best {
+            Some(removed) if removed.is_empty() => Verdict::Safe,
+            Some(removed) => Verdict::Fixable(removed),
+            None => Verdict::Unsafe,
+        }
+    }
+}
+
+fn parse_args() -> Result<(Rules, bool), String> {
+    let mut rules = Rules::default();
+    let mut list = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut number = || {
+            args.next()
+                .and_then(|n| n.parse::<i32>().ok())
+                .filter(|&n| n >= 0)
+                .ok_or(format!("{} needs a non-negative number", arg))
+        };
+        match arg.as_str() {
+            "--min-step" => rules.min_step = number()?,
+            "--max-step" => rules.max_step = number()?,
+            "--removals" => rules.max_removals = number()? as usize,
+            "--monotonicity" => {
+                rules.monotonicity = match args.next().as_deref() {
+                    Some("strict") => Monotonicity::Strict,
+                    Some("non-strict") => Monotonicity::NonStrict,
+                    Some("any") => Monotonicity::Any,
+                    _ => return Err("--monotonicity needs strict, non-strict or any".to_string()),
+                }
+            }
+            "--list" => list = true,
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ => return Err(format!("Unexpected argument: {}\n\n{}", arg, USAGE)),
+        }
+    }
+    if rules.min_step > rules.max_step {
+        return Err("--min-step can't be larger than --max-step".to_string());
+    }
+    Ok((rules, list))
+}
+
+fn main() {
+    let (rules, list) = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let reports = INPUT.lines().map(|l| {
+        l.split_whitespace()
+            .map(|n| n.parse::<i32>().unwrap())
+            .collect::<Vec<i32>>()
+    });
+
+    let mut really_safe_count = 0;
+    let mut fixable_count = 0;
+    for (line, report) in reports.enumerate() {
+        match rules.check(&report) {
+            Verdict::Safe => really_safe_count += 1,
+            Verdict::Fixable(indizes) => {
+                fixable_count += 1;
+                if list {
+                    let levels: Vec<i32> = indizes.iter().map(|&i| report[i]).collect();
+                    println!(
+                        "Line {} {:?} is fixable by removing indizes {:?} (levels {:?})",
+                        line + 1,
+                        report,
+                        indizes,
+                        levels
+                    );
+                }
+            }
+            Verdict::Unsafe => {}
+        }
+    }
+
+    println!("Really safe: {}", really_safe_count);
+    println!("Fixable: {}", fixable_count);
+    println!("Total: {}", really_safe_count + fixable_count);
+}
+
+#[test]
+fn test_check() {
+    let rules = Rules::default();
+    assert_eq!(rules.check(&[7, 6, 4, 2, 1]), Verdict::Safe);
+    assert_eq!(rules.check(&[1, 2, 7, 8, 9]), Verdict::Unsafe);
+    assert_eq!(rules.check(&[9, 7, 6, 2, 1]), Verdict::Unsafe);
+    assert_eq!(rules.check(&[1, 3, 2, 4, 5]), Verdict::Fixable(vec![1]));
+    assert_eq!(rules.check(&[8, 6, 4, 4, 1]), Verdict::Fixable(vec![2]));
+    assert_eq!(rules.check(&[1, 3, 6, 7, 9]), Verdict::Safe);
+    // The first level has to go, which only shows up in the other direction
+    assert_eq!(rules.check(&[5, 1, 2, 3]), Verdict::Fixable(vec![0]));
+    assert_eq!(rules.check(&[1, 2, 3, 3]), Verdict::Fixable(vec![2]));
+}
+
+#[test]
+fn test_generalized_rules() {
+    let two_removals = Rules {
+        max_removals: 2,
+        ..Rules::default()
+    };
+    assert_eq!(
+        two_removals.check(&[1, 2, 7, 8, 9]),
+        Verdict::Fixable(vec![0, 1])
+    );
+    assert_eq!(
+        two_removals.check(&[9, 1, 2, 9, 3]),
+        Verdict::Fixable(vec![0, 3])
+    );
+    assert_eq!(two_removals.check(&[1, 9, 9, 9]), Verdict::Unsafe);
+
+    let non_strict = Rules {
+        min_step: 0,
+        monotonicity: Monotonicity::NonStrict,
+        max_removals: 0,
+        ..Rules::default()
+    };
+    assert_eq!(non_strict.check(&[8, 6, 4, 4, 1]), Verdict::Safe);
+    assert_eq!(non_strict.check(&[1, 2, 2, 1]), Verdict::Unsafe);
+
+    let any = Rules {
+        monotonicity: Monotonicity::Any,
+        max_removals: 0,
+        ..Rules::default()
+    };
+    assert_eq!(any.check(&[1, 3, 2, 4, 5]), Verdict::Safe);
+    assert_eq!(any.check(&[1, 3, 3, 4, 5]), Verdict::Unsafe);
+}
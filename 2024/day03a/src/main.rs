@@ -0,0 +1,160 @@
+use std::{fs::File, io::Read, time::Instant};
+
+use aoc_utils::scan::StreamScanner;
+use memchr::memmem;
+use nom::{
+    bytes::complete::tag,
+    character::complete::digit1,
+    combinator::map_res,
+    error::{Error, ErrorKind},
+    sequence::{delimited, separated_pair},
+    Err, IResult,
+};
+
+const INPUT: &'static str = include_str!("input.txt");
+
+/// The longest possible instruction, `mul(123,456)`
+const MAX_MUL_LEN: usize = 12;
+
+/// Where an instruction was found in the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Span {
+    /// Byte offset in the input
+    offset: u64,
+    len: usize,
+}
+
+fn parse_number(input: &str) -> IResult<&str, u32> {
+    map_res(digit1, |s: &str| {
+        if s.len() <= 3 {
+            match s.parse::<u32>() {
+                Ok(n) => Ok(n.into()),
+                Err(_) => Err(Err::Error("Invalid number")),
+            }
+        } else {
+            Err(Err::Error("Number too large"))
+        }
+    })(input)
+}
+
+fn parse_tuple(input: &str) -> IResult<&str, (u32, u32)> {
+    let (input, (x, y)) = separated_pair(parse_number, tag(","), parse_number)(input)?;
+    Ok((input, (x, y)))
+}
+
+fn parse_mul(input: &str) -> IResult<&str, (u32, u32)> {
+    let (input, parsed) = delimited(tag("mul("), parse_tuple, tag(")"))(input)?;
+
+    Ok((input, parsed))
+}
+
+/// The next instruction, with its span relative to `input`
+fn parse_one_spanned(input: &str) -> IResult<&str, ((u32, u32), Span)> {
+    // Skip garbage by jumping to the next `mul(`, the parser can't match anywhere else
+    for start in memmem::find_iter(input.as_bytes(), "mul(") {
+        if let Ok((rest, x)) = parse_mul(&input[start..]) {
+            let span = Span {
+                offset: start as u64,
+                len: input.len() - start - rest.len(),
+            };
+            return Ok((rest, (x, span)));
+        }
+    }
+    Err(Err::Error(Error::new(input, ErrorKind::ManyTill)))
+}
+
+/// Like `parse_one_spanned`, without the span
+#[cfg(test)]
+fn parse_one(input: &str) -> IResult<&str, (u32, u32)> {
+    parse_one_spanned(input).map(|(rest, (x, _))| (rest, x))
+}
+
+/// Like `parse_all_spanned`, but reads the input in chunks
+fn scan(input: impl Read) -> std::io::Result<Vec<((u32, u32), Span)>> {
+    let mut scanner = StreamScanner::new(input, &["mul("], MAX_MUL_LEN);
+    let mut result = vec![];
+    while let Some(m) = scanner.next_match(|s| {
+        let (rest, x) = parse_mul(s).ok()?;
+        Some((x, s.len() - rest.len()))
+    })? {
+        let span = Span {
+            offset: m.offset,
+            len: m.len,
+        };
+        result.push((m.value, span));
+    }
+    Ok(result)
+}
+
+/// All instructions, with their spans in `input`
+fn parse_all_spanned(input: &str) -> Vec<((u32, u32), Span)> {
+    let mut result = vec![];
+    let mut offset = 0;
+    let mut rest = input;
+    while let Ok((new_rest, (x, mut span))) = parse_one_spanned(rest) {
+        span.offset += offset;
+        offset += (rest.len() - new_rest.len()) as u64;
+        rest = new_rest;
+        result.push((x, span));
+    }
+    result
+}
+
+/// Like `parse_all_spanned`, without the spans
+#[cfg(test)]
+fn parse_all(input: &str) -> Vec<(u32, u32)> {
+    parse_all_spanned(input)
+        .into_iter()
+        .map(|(x, _)| x)
+        .collect()
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(parse_one("mul(1,2)"), Ok(("", (1, 2))));
+    assert_eq!(parse_one("mul(111,222)"), Ok(("", (111, 222))));
+    assert!(parse_one("mul(111,2222)").is_err());
+
+    assert_eq!(parse_one("garbagemul(111,333)"), Ok(("", (111, 333))));
+    assert_eq!(
+        parse_one("garbagemul(111,333)moregarbage"),
+        Ok(("moregarbage", (111, 333)))
+    );
+
+    assert_eq!(parse_all("mul(1,2)"), vec![(1, 2)]);
+    assert_eq!(parse_all("mul(1,2)mul(3,4)"), vec![(1, 2), (3, 4)]);
+    assert_eq!(parse_all("mul(1,2)mul(*mul(5,6)"), vec![(1, 2), (5, 6)]);
+}
+
+#[test]
+fn test_spans() {
+    let input = "xmul(1,2)mul(*mul(55,6)!";
+    let spans =
+        |all: Vec<((u32, u32), Span)>| all.into_iter().map(|(_, span)| span).collect::<Vec<_>>();
+    let expected = vec![Span { offset: 1, len: 8 }, Span { offset: 14, len: 9 }];
+    assert_eq!(spans(parse_all_spanned(input)), expected);
+    assert_eq!(spans(scan(input.as_bytes()).unwrap()), expected);
+    assert_eq!(scan(input.as_bytes()).unwrap(), parse_all_spanned(input),);
+    assert_eq!(&input[14..23], "mul(55,6)");
+}
+
+fn main() {
+    let start = Instant::now();
+
+    // Optionally stream the input from a file, which may be larger than memory
+    let tuples = match std::env::args().nth(1) {
+        Some(path) => File::open(&path)
+            .and_then(scan)
+            .expect("Failed to read the input"),
+        None => parse_all_spanned(INPUT),
+    };
+    let result = tuples
+        .iter()
+        .map(|&((x, y), _)| x as u64 * y as u64)
+        .sum::<u64>();
+
+    let elapsed = start.elapsed();
+
+    println!("Result: {}", result);
+    println!("(took: {:?})", elapsed);
+}
@@ -0,0 +1,191 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State {
+    /// Toggled by `do()` and `don't()`
+    pub enabled: bool,
+    pub accumulator: i64,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State {
+            enabled: true,
+            accumulator: 0,
+        }
+    }
+}
+
+pub type Execute = fn(&mut State, &[u32]);
+
+pub struct Opcode {
+    pub name: &'static str,
+    pub arity: usize,
+    /// Whether the opcode is skipped while the state is disabled
+    pub requires_enabled: bool,
+    pub execute: Execute,
+}
+
+/// The opcodes that are recognized in the corrupted memory
+#[derive(Default)]
+pub struct InstructionSet {
+    opcodes: Vec<Opcode>,
+}
+
+impl InstructionSet {
+    pub fn register(
+        &mut self,
+        name: &'static str,
+        arity: usize,
+        requires_enabled: bool,
+        execute: Execute,
+    ) -> &mut Self {
+        assert!(
+            self.opcodes.iter().all(|o| o.name != name),
+            "Opcode {} is already registered",
+            name
+        );
+        self.opcodes.push(Opcode {
+            name,
+            arity,
+            requires_enabled,
+            execute,
+        });
+        self
+    }
+
+    pub fn opcode(&self, instruction: &Instruction) -> &Opcode {
+        &self.opcodes[instruction.opcode]
+    }
+
+    pub fn opcodes(&self) -> &[Opcode] {
+        &self.opcodes
+    }
+
+    /// The longest possible instruction, with all arguments having three digits
+    pub fn max_len(&self) -> usize {
+        self.opcodes
+            .iter()
+            .map(|o| o.name.len() + 2 + 3 * o.arity + o.arity.saturating_sub(1))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// `mul(a,b)`, `do()` and `don't()` from the puzzle
+    pub fn standard() -> Self {
+        let mut set = InstructionSet::default();
+        set.register("mul", 2, true, |state, args| {
+            state.accumulator += args[0] as i64 * args[1] as i64
+        })
+        .register("do", 0, false, |state, _| state.enabled = true)
+        .register("don't", 0, false, |state, _| state.enabled = false);
+        set
+    }
+
+    /// The standard opcodes plus `add(a,b)`, `sub(a,b)` and `reset()`
+    pub fn extended() -> Self {
+        let mut set = Self::standard();
+        set.register("add", 2, true, |state, args| {
+            state.accumulator += args[0] as i64 + args[1] as i64
+        })
+        .register("sub", 2, true, |state, args| {
+            state.accumulator += args[0] as i64 - args[1] as i64
+        })
+        .register("reset", 0, true, |state, _| state.accumulator = 0);
+        set
+    }
+
+    pub fn display<'a>(&'a self, instruction: &'a Instruction) -> impl Display + 'a {
+        DisplayInstruction {
+            set: self,
+            instruction,
+        }
+    }
+}
+
+/// Where an instruction was found in the input
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// Byte offset in the input
+    pub offset: u64,
+    pub len: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    /// Index into the opcodes of the instruction set
+    pub opcode: usize,
+    pub args: Vec<u32>,
+    pub span: Span,
+}
+
+struct DisplayInstruction<'a> {
+    set: &'a InstructionSet,
+    instruction: &'a Instruction,
+}
+
+impl Display for DisplayInstruction<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let args: Vec<String> = self
+            .instruction
+            .args
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+        write!(
+            f,
+            "{}({})",
+            self.set.opcodes[self.instruction.opcode].name,
+            args.join(",")
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub instruction: Instruction,
+    /// False if the instruction was skipped because the state was disabled
+    pub executed: bool,
+    /// The state after the instruction
+    pub state: State,
+}
+
+pub struct Interpreter<'a> {
+    set: &'a InstructionSet,
+    pub state: State,
+    /// Only recorded if enabled in `new`
+    pub trace: Option<Vec<TraceEntry>>,
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new(set: &'a InstructionSet, trace: bool) -> Self {
+        Interpreter {
+            set,
+            state: State::default(),
+            trace: trace.then(Vec::new),
+        }
+    }
+
+    pub fn step(&mut self, instruction: Instruction) {
+        let opcode = &self.set.opcodes[instruction.opcode];
+        assert_eq!(opcode.arity, instruction.args.len());
+
+        let executed = self.state.enabled || !opcode.requires_enabled;
+        if executed {
+            (opcode.execute)(&mut self.state, &instruction.args);
+        }
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEntry {
+                instruction,
+                executed,
+                state: self.state,
+            });
+        }
+    }
+
+    pub fn run(&mut self, instructions: impl IntoIterator<Item = Instruction>) {
+        for instruction in instructions {
+            self.step(instruction);
+        }
+    }
+}
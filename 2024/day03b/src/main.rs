@@ -0,0 +1,195 @@
+use std::{
+    fs::{self, File},
+    io::{self, Read},
+};
+
+use aoc_utils::scan::{Match, StreamScanner};
+use interpreter::{Instruction, InstructionSet, Interpreter, Span};
+use nom::{
+    bytes::complete::tag,
+    character::complete::digit1,
+    combinator::map_res,
+    error::{Error, ErrorKind},
+    multi::separated_list0,
+    sequence::{delimited, preceded},
+    Err, IResult,
+};
+
+mod interpreter;
+mod render;
+
+const INPUT: &'static str = include_str!("input.txt");
+
+fn parse_number(input: &str) -> IResult<&str, u32> {
+    map_res(digit1, |s: &str| {
+        if s.len() <= 3 {
+            match s.parse::<u32>() {
+                Ok(n) => Ok(n.into()),
+                Err(_) => Err(Err::Error("Invalid number")),
+            }
+        } else {
+            Err(Err::Error("Number too large"))
+        }
+    })(input)
+}
+
+fn parse_arguments(input: &str) -> IResult<&str, Vec<u32>> {
+    delimited(tag("("), separated_list0(tag(","), parse_number), tag(")"))(input)
+}
+
+/// Parses a call of any opcode in the set, with exactly as many arguments as its arity
+fn parse_instruction<'a>(set: &InstructionSet, input: &'a str) -> IResult<&'a str, Instruction> {
+    for (opcode, op) in set.opcodes().iter().enumerate() {
+        if let Ok((rest, args)) = preceded(tag(op.name), parse_arguments)(input) {
+            if args.len() == op.arity {
+                let instruction = Instruction {
+                    opcode,
+                    args,
+                    span: Span::default(),
+                };
+                return Ok((rest, instruction));
+            }
+        }
+    }
+    Err(Err::Error(Error::new(input, ErrorKind::Alt)))
+}
+
+/// Finds all instructions in the input, which is read in chunks. Only the offsets where
+/// an opcode name followed by `(` starts are parsed, everything else is skipped.
+fn scan(
+    set: &InstructionSet,
+    input: impl Read,
+    mut f: impl FnMut(Match<Instruction>),
+) -> io::Result<()> {
+    let prefixes: Vec<String> = set
+        .opcodes()
+        .iter()
+        .map(|o| format!("{}(", o.name))
+        .collect();
+    let prefixes: Vec<&str> = prefixes.iter().map(|p| p.as_str()).collect();
+    let mut scanner = StreamScanner::new(input, &prefixes, set.max_len());
+    while let Some(mut m) = scanner.next_match(|s| {
+        let (rest, instruction) = parse_instruction(set, s).ok()?;
+        Some((instruction, s.len() - rest.len()))
+    })? {
+        m.value.span = Span {
+            offset: m.offset,
+            len: m.len,
+        };
+        f(m);
+    }
+    Ok(())
+}
+
+fn parse_all(set: &InstructionSet, input: &str) -> Vec<Instruction> {
+    let mut result = vec![];
+    scan(set, input.as_bytes(), |m| result.push(m.value)).unwrap();
+    result
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Result,
+    /// One line per instruction with the state after it
+    Trace,
+    /// The input with the instructions colored by whether they were executed
+    Highlight,
+    Json,
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let flag = |name: &str| args.iter().any(|a| a == name);
+    let set = if flag("--extended") {
+        InstructionSet::extended()
+    } else {
+        InstructionSet::standard()
+    };
+    let mode = if flag("--json") {
+        Mode::Json
+    } else if flag("--highlight") {
+        Mode::Highlight
+    } else if flag("--trace") {
+        Mode::Trace
+    } else {
+        Mode::Result
+    };
+    let path = args.iter().find(|a| !a.starts_with("--"));
+
+    // Highlighting needs the whole input, otherwise a file is streamed as it may be larger
+    // than memory
+    let input = match path {
+        Some(path) if mode == Mode::Highlight => {
+            fs::read_to_string(path).expect("Failed to read the input")
+        }
+        Some(_) => String::new(),
+        None => INPUT.to_string(),
+    };
+    let mut interpreter = Interpreter::new(&set, mode != Mode::Result);
+    match path {
+        Some(path) if mode != Mode::Highlight => File::open(path)
+            .and_then(|file| scan(&set, file, |m| interpreter.step(m.value)))
+            .expect("Failed to read the input"),
+        _ => interpreter.run(parse_all(&set, &input)),
+    }
+
+    let trace = interpreter.trace.as_deref().unwrap_or_default();
+    let result = interpreter.state.accumulator;
+    match mode {
+        Mode::Result => {}
+        Mode::Trace => {
+            for entry in trace {
+                println!(
+                    "{:>8} {:<16} {:<8} enabled={:<5} accumulator={}",
+                    entry.instruction.span.offset,
+                    set.display(&entry.instruction).to_string(),
+                    if entry.executed {
+                        "executed"
+                    } else {
+                        "skipped"
+                    },
+                    entry.state.enabled,
+                    entry.state.accumulator
+                );
+            }
+        }
+        Mode::Highlight => {
+            print!("{}", render::highlight(&input, trace));
+            if !input.ends_with('\n') {
+                println!();
+            }
+        }
+        Mode::Json => {
+            println!("{}", render::json(&set, trace, result));
+            return;
+        }
+    }
+
+    println!("Result: {}", result);
+}
+
+#[test]
+fn test_interpreter() {
+    let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+    let set = InstructionSet::standard();
+    let instructions = parse_all(&set, input);
+    assert_eq!(instructions.len(), 6);
+    assert_eq!(set.display(&instructions[1]).to_string(), "don't()");
+
+    let mut interpreter = Interpreter::new(&set, true);
+    interpreter.run(instructions);
+    assert_eq!(interpreter.state.accumulator, 48);
+    let executed: Vec<bool> = interpreter
+        .trace
+        .unwrap()
+        .iter()
+        .map(|e| e.executed)
+        .collect();
+    assert_eq!(executed, vec![true, true, false, false, true, true]);
+
+    let set = InstructionSet::extended();
+    let instructions = parse_all(&set, "mul(2,3)add(1,2)reset(1)sub(1,5)reset()add(4,4)");
+    let mut interpreter = Interpreter::new(&set, false);
+    interpreter.run(instructions);
+    assert_eq!(interpreter.state.accumulator, 8);
+}
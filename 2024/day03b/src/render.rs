@@ -0,0 +1,104 @@
+use serde::Serialize;
+
+use crate::interpreter::{InstructionSet, TraceEntry};
+
+const EXECUTED: &str = "\x1b[1;32m";
+const SKIPPED: &str = "\x1b[1;31m";
+const GARBAGE: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+fn push_colored(out: &mut String, color: &str, text: &str) {
+    if !text.is_empty() {
+        out.push_str(color);
+        out.push_str(text);
+        out.push_str(RESET);
+    }
+}
+
+/// The input with executed instructions in green, instructions skipped because of `don't()`
+/// in red, and the garbage in between dimmed
+pub fn highlight(input: &str, trace: &[TraceEntry]) -> String {
+    let mut out = String::with_capacity(input.len() * 2);
+    let mut cursor = 0;
+    for entry in trace {
+        let start = entry.instruction.span.offset as usize;
+        let end = start + entry.instruction.span.len;
+        push_colored(&mut out, GARBAGE, &input[cursor..start]);
+        let color = if entry.executed { EXECUTED } else { SKIPPED };
+        push_colored(&mut out, color, &input[start..end]);
+        cursor = end;
+    }
+    push_colored(&mut out, GARBAGE, &input[cursor..]);
+    out
+}
+
+#[derive(Serialize)]
+struct JsonInstruction<'a> {
+    offset: u64,
+    len: usize,
+    text: String,
+    opcode: &'a str,
+    args: &'a [u32],
+    executed: bool,
+    accumulator: i64,
+}
+
+#[derive(Serialize)]
+struct JsonOutput<'a> {
+    result: i64,
+    instructions: Vec<JsonInstruction<'a>>,
+}
+
+/// The result and all instructions with their spans as JSON
+pub fn json(set: &InstructionSet, trace: &[TraceEntry], result: i64) -> String {
+    let instructions = trace
+        .iter()
+        .map(|entry| {
+            let instruction = &entry.instruction;
+            JsonInstruction {
+                offset: instruction.span.offset,
+                len: instruction.span.len,
+                text: set.display(instruction).to_string(),
+                opcode: set.opcode(instruction).name,
+                args: &instruction.args,
+                executed: entry.executed,
+                accumulator: entry.state.accumulator,
+            }
+        })
+        .collect();
+    serde_json::to_string(&JsonOutput {
+        result,
+        instructions,
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_render() {
+    use crate::interpreter::Interpreter;
+
+    let input = "xmul(2,4)don't()_mul(5,5)do()?";
+    let set = InstructionSet::standard();
+    let mut interpreter = Interpreter::new(&set, true);
+    interpreter.run(crate::parse_all(&set, input));
+    let trace = interpreter.trace.unwrap();
+
+    assert_eq!(
+        highlight(input, &trace),
+        format!(
+            "{G}x{R}{E}mul(2,4){R}{E}don't(){R}{G}_{R}{S}mul(5,5){R}{E}do(){R}{G}?{R}",
+            G = GARBAGE,
+            E = EXECUTED,
+            S = SKIPPED,
+            R = RESET
+        )
+    );
+
+    let json = json(&set, &trace[..2], 8);
+    assert_eq!(
+        json,
+        "{\"result\":8,\"instructions\":[\
+            {\"offset\":1,\"len\":8,\"text\":\"mul(2,4)\",\"opcode\":\"mul\",\"args\":[2,4],\"executed\":true,\"accumulator\":8},\
+            {\"offset\":9,\"len\":7,\"text\":\"don't()\",\"opcode\":\"don't\",\"args\":[],\"executed\":true,\"accumulator\":8}]}"
+    );
+}
@@ -0,0 +1,292 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Instant,
+};
+
+const INPUT: &'static str = include_str!("input.txt");
+
+const USAGE: &str = "Usage: day04a [--words <word,...>] [--wrap] [--hits]
+
+Counts the words (default: XMAS) in all 8 directions. With --wrap, words can continue
+on the opposite edge of the grid. --hits prints the position and direction of every hit.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Right,
+    Down,
+    Left,
+    Up,
+    DownRight,
+    DownLeft,
+    UpRight,
+    UpLeft,
+}
+
+impl Direction {
+    const ALL: [Direction; 8] = [
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+        Direction::Up,
+        Direction::DownRight,
+        Direction::DownLeft,
+        Direction::UpRight,
+        Direction::UpLeft,
+    ];
+
+    /// `(dx, dy)`, y grows downwards
+    fn delta(self) -> (i64, i64) {
+        match self {
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Up => (0, -1),
+            Direction::DownRight => (1, 1),
+            Direction::DownLeft => (-1, 1),
+            Direction::UpRight => (1, -1),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Hit<'w> {
+    word: &'w str,
+    /// Position of the first letter
+    x: usize,
+    y: usize,
+    direction: Direction,
+}
+
+struct WordSearch {
+    chars: Vec<Vec<char>>,
+    width: usize,
+    height: usize,
+    /// Whether words can leave the grid on one edge and continue on the opposite one
+    wrap: bool,
+}
+
+impl WordSearch {
+    fn parse(input: &str, wrap: bool) -> Self {
+        let chars: Vec<Vec<char>> = input
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|line| line.chars().collect())
+            .collect();
+        let width = chars.first().map_or(0, |l| l.len());
+        assert!(
+            chars.iter().all(|l| l.len() == width),
+            "All lines must have the same length"
+        );
+        WordSearch {
+            height: chars.len(),
+            chars,
+            width,
+            wrap,
+        }
+    }
+
+    fn get(&self, x: i64, y: i64) -> Option<char> {
+        let (x, y) = if self.wrap {
+            (
+                x.rem_euclid(self.width as i64),
+                y.rem_euclid(self.height as i64),
+            )
+        } else if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return None;
+        } else {
+            (x, y)
+        };
+        Some(self.chars[y as usize][x as usize])
+    }
+
+    /// The cells covered by a hit
+    fn cells(&self, hit: &Hit) -> Vec<(usize, usize)> {
+        let (dx, dy) = hit.direction.delta();
+        (0..hit.word.chars().count() as i64)
+            .map(|i| {
+                let (x, y) = (hit.x as i64 + dx * i, hit.y as i64 + dy * i);
+                (
+                    x.rem_euclid(self.width as i64) as usize,
+                    y.rem_euclid(self.height as i64) as usize,
+                )
+            })
+            .collect()
+    }
+
+    /// All hits of the word. A one-letter word is found once per cell, going right.
+    fn find<'w>(&self, word: &'w str) -> Vec<Hit<'w>> {
+        let letters: Vec<char> = word.chars().collect();
+        let Some(&first) = letters.first() else {
+            return vec![];
+        };
+        let directions: &[Direction] = if letters.len() == 1 {
+            &[Direction::Right]
+        } else {
+            &Direction::ALL
+        };
+
+        let mut hits = vec![];
+        // When wrapping around a narrow grid, several directions can read the same cells
+        let mut seen = BTreeSet::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.chars[y][x] != first {
+                    continue;
+                }
+                for &direction in directions {
+                    let (dx, dy) = direction.delta();
+                    let found = letters.iter().enumerate().skip(1).all(|(i, &letter)| {
+                        let i = i as i64;
+                        self.get(x as i64 + dx * i, y as i64 + dy * i) == Some(letter)
+                    });
+                    if !found {
+                        continue;
+                    }
+                    let hit = Hit {
+                        word,
+                        x,
+                        y,
+                        direction,
+                    };
+                    if !self.wrap || seen.insert(self.cells(&hit)) {
+                        hits.push(hit);
+                    }
+                }
+            }
+        }
+        hits
+    }
+
+    /// How many cells are covered by more than one hit
+    fn overlapping_cells(&self, hits: &[Hit]) -> usize {
+        let mut covered = BTreeMap::<(usize, usize), usize>::new();
+        for hit in hits {
+            for cell in self.cells(hit) {
+                *covered.entry(cell).or_default() += 1;
+            }
+        }
+        covered.values().filter(|&&count| count > 1).count()
+    }
+}
+
+fn main() {
+    let mut words = vec!["XMAS".to_string()];
+    let mut wrap = false;
+    let mut show_hits = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--words" => {
+                let Some(list) = args.next() else {
+                    eprintln!("{}", USAGE);
+                    std::process::exit(1);
+                };
+                words = list.split(',').map(|w| w.trim().to_string()).collect();
+            }
+            "--wrap" => wrap = true,
+            "--hits" => show_hits = true,
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let start = Instant::now();
+
+    let search = WordSearch::parse(INPUT, wrap);
+    let mut hits = vec![];
+    for word in &words {
+        let found = search.find(word);
+        if words.len() > 1 {
+            println!("Count {}: {}", word, found.len());
+        }
+        hits.extend(found);
+    }
+    let overlapping = search.overlapping_cells(&hits);
+
+    let elapsed = start.elapsed();
+
+    if show_hits {
+        for hit in &hits {
+            println!(
+                "{} at ({}, {}) going {:?}",
+                hit.word, hit.x, hit.y, hit.direction
+            );
+        }
+    }
+    println!("Overlapping cells: {}", overlapping);
+    println!("Result: {}", hits.len());
+    println!("(took: {:?})", elapsed);
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+#[test]
+fn test_find() {
+    let search = WordSearch::parse(EXAMPLE, false);
+    let hits = search.find("XMAS");
+    assert_eq!(hits.len(), 18);
+    assert_eq!(
+        hits[0],
+        Hit {
+            word: "XMAS",
+            x: 4,
+            y: 0,
+            direction: Direction::DownRight
+        }
+    );
+    assert_eq!(search.find("SAMX").len(), 18);
+    assert_eq!(search.find("").len(), 0);
+
+    // Both hits start on the same `X`
+    let small = WordSearch::parse("XMAS\nM...\nA...\nS...", false);
+    let hits = small.find("XMAS");
+    assert_eq!(hits.len(), 2);
+    assert_eq!(small.overlapping_cells(&hits), 1);
+}
+
+#[test]
+fn test_wrap() {
+    let search = WordSearch::parse("ASXM\n....", true);
+    let hits = search.find("XMAS");
+    assert_eq!(
+        hits,
+        vec![Hit {
+            word: "XMAS",
+            x: 2,
+            y: 0,
+            direction: Direction::Right
+        }]
+    );
+    assert_eq!(search.cells(&hits[0]), vec![(2, 0), (3, 0), (0, 0), (1, 0)]);
+    assert!(WordSearch::parse("ASXM\n....", false)
+        .find("XMAS")
+        .is_empty());
+
+    // Down, down left and down right all read the same cells of a single column
+    let column = WordSearch::parse("X\nM\nA\nS", true);
+    assert_eq!(column.find("XMAS").len(), 1);
+    let row = WordSearch::parse("XMAS", true);
+    assert_eq!(row.find("XMAS").len(), 1);
+    assert_eq!(row.find("SAMX").len(), 1);
+}
+
+#[test]
+fn test_single_letter() {
+    let search = WordSearch::parse(EXAMPLE, false);
+    let xs = EXAMPLE.chars().filter(|&c| c == 'X').count();
+    assert_eq!(search.find("X").len(), xs);
+    assert_eq!(WordSearch::parse(EXAMPLE, true).find("X").len(), xs);
+}
@@ -0,0 +1,64 @@
+use aoc_utils::pattern::{Matcher, Pattern};
+
+const INPUT: &'static str = include_str!("input.txt");
+
+/// Two `MAS` crossing on the `A`, in all orientations
+const X_MAS: &str = "M.S / .A. / M.S";
+
+const USAGE: &str = "Usage: day04b [--pattern <rows separated by />] [--positions]";
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let mut pattern = X_MAS.to_string();
+    let mut positions = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--pattern" => {
+                let Some(rows) = args.next() else {
+                    eprintln!("{}", USAGE);
+                    std::process::exit(1);
+                };
+                pattern = rows;
+            }
+            "--positions" => positions = true,
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let chars: Vec<Vec<_>> = INPUT.lines().map(|line| line.chars().collect()).collect();
+    let pattern = Pattern::parse(&pattern).unwrap_or_else(|e| {
+        eprintln!("Invalid pattern: {}\n{}", e, USAGE);
+        std::process::exit(1);
+    });
+    let matcher = Matcher::new(&pattern);
+    let matches = matcher.find(&chars);
+
+    if positions {
+        for m in &matches {
+            println!("({}, {}):\n{:?}", m.x, m.y, matcher.variants()[m.variant]);
+        }
+    }
+
+    println!("Result: {}", matches.len());
+}
+
+#[test]
+fn test_x_mas() {
+    let example = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+    let chars: Vec<Vec<_>> = example.lines().map(|line| line.chars().collect()).collect();
+    let matcher = Matcher::new(&Pattern::parse(X_MAS).unwrap());
+    assert_eq!(matcher.variants().len(), 4);
+    assert_eq!(matcher.count(&chars), 9);
+}
@@ -0,0 +1,461 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Instant,
+};
+
+use aoc_utils::graph::Graph;
+
+const INPUT: &'static str = include_str!("input.txt");
+
+const USAGE: &str = "Usage: day05b [--explain] [--analyze]
+
+Sums the middle pages of the incorrect updates after sorting them. --explain prints the
+rules every incorrect update breaks, and the fewest pages to move to fix it. --analyze
+prints what the rule set implies as a whole: implied and redundant rules, pages without
+rules, and whether the rules are acyclic.";
+
+/// For each page, the pages that have to come after it
+type Rules = BTreeMap<u32, BTreeSet<u32>>;
+
+fn parse(input: &str) -> (Rules, Vec<Vec<u32>>) {
+    let mut rules: Rules = BTreeMap::new();
+    let mut updates = Vec::new();
+    let mut rules_done = false;
+
+    for line in input.lines() {
+        if line.is_empty() {
+            rules_done = true;
+            continue;
+        }
+
+        if !rules_done {
+            let mut parts = line.split('|');
+            let left: u32 = parts.next().unwrap().parse().unwrap();
+            let right: u32 = parts.next().unwrap().parse().unwrap();
+
+            let after = rules.entry(left).or_default();
+            after.insert(right);
+            continue;
+        }
+
+        updates.push(line.split(',').map(|x| x.parse().unwrap()).collect());
+    }
+
+    (rules, updates)
+}
+
+fn is_correct(rules: &Rules, pages: &[u32]) -> bool {
+    let mut seen: BTreeSet<u32> = BTreeSet::new();
+    for entry in pages.iter() {
+        if let Some(comes_before) = rules.get(entry) {
+            if !seen.is_disjoint(comes_before) {
+                // We've seen a page that should come after this one. Not correct.
+                return false;
+            }
+        }
+        seen.insert(*entry);
+    }
+    true
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum UpdateOrder {
+    /// The only order of the pages that satisfies the rules
+    Unique(Vec<u32>),
+    /// Some pages have no rule between them, so there are several valid orders.
+    /// Those pages keep their relative position from the update.
+    Ambiguous(Vec<u32>),
+    /// The rules between the pages contradict each other, each page has to come before the next
+    Cycle(Vec<u32>),
+}
+
+/// Sorts the pages topologically, only using the rules between pages of this update
+fn order(rules: &Rules, pages: &[u32]) -> UpdateOrder {
+    let mut graph = Graph::directed();
+    // Kahn's algorithm breaks ties by index, which is the position in the update
+    for &page in pages {
+        graph.add_node(page);
+    }
+    for &page in pages {
+        for &after in rules.get(&page).into_iter().flatten() {
+            if graph.index_of(&after).is_some() {
+                graph.add_edge(page, after);
+            }
+        }
+    }
+
+    match graph.toposort() {
+        Ok(order) => {
+            // Only if there's a rule between all neighbors, no other page could have been picked
+            let unique = order.windows(2).all(|w| graph.has_edge(w[0], w[1]));
+            let order = order.into_iter().map(|n| *graph.name(n)).collect();
+            if unique {
+                UpdateOrder::Unique(order)
+            } else {
+                UpdateOrder::Ambiguous(order)
+            }
+        }
+        Err(cycle) => UpdateOrder::Cycle(cycle.into_iter().map(|n| *graph.name(n)).collect()),
+    }
+}
+
+/// A rule `before|after` that an update breaks by putting `after` first
+#[derive(Debug, PartialEq, Eq)]
+struct Violation {
+    before: u32,
+    after: u32,
+    /// Positions of the pages in the update
+    before_position: usize,
+    after_position: usize,
+}
+
+fn violations(rules: &Rules, pages: &[u32]) -> Vec<Violation> {
+    let mut violations = vec![];
+    for (after_position, &after) in pages.iter().enumerate() {
+        for (before_position, &before) in pages.iter().enumerate().skip(after_position + 1) {
+            if rules.get(&before).is_some_and(|r| r.contains(&after)) {
+                violations.push(Violation {
+                    before,
+                    after,
+                    before_position,
+                    after_position,
+                });
+            }
+        }
+    }
+    violations
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Move {
+    page: u32,
+    from: usize,
+    /// Position in the sorted update
+    to: usize,
+}
+
+/// The fewest pages that have to be moved to turn `pages` into `sorted`: all pages except the
+/// longest subsequence that is already in sorted order. If the order isn't unique, this is only
+/// minimal for the given `sorted`.
+fn moves(pages: &[u32], sorted: &[u32]) -> Vec<Move> {
+    let target: BTreeMap<u32, usize> = sorted.iter().enumerate().map(|(i, &p)| (p, i)).collect();
+    let targets: Vec<usize> = pages.iter().map(|p| target[p]).collect();
+
+    // Longest increasing subsequence of the targets. `tails[k]` is the position of the smallest
+    // target that ends an increasing subsequence of length `k + 1`.
+    let mut tails: Vec<usize> = vec![];
+    let mut previous = vec![None; pages.len()];
+    for (i, &t) in targets.iter().enumerate() {
+        let k = tails.partition_point(|&j| targets[j] < t);
+        previous[i] = k.checked_sub(1).map(|k| tails[k]);
+        if k == tails.len() {
+            tails.push(i);
+        } else {
+            tails[k] = i;
+        }
+    }
+
+    let mut keep = vec![false; pages.len()];
+    let mut current = tails.last().copied();
+    while let Some(i) = current {
+        keep[i] = true;
+        current = previous[i];
+    }
+
+    (0..pages.len())
+        .filter(|&i| !keep[i])
+        .map(|i| Move {
+            page: pages[i],
+            from: i,
+            to: targets[i],
+        })
+        .collect()
+}
+
+fn explain(rules: &Rules, pages: &[u32], sorted: &[u32]) {
+    for v in violations(rules, pages) {
+        println!(
+            "  rule {}|{} broken by {} at {} and {} at {}",
+            v.before, v.after, v.after, v.after_position, v.before, v.before_position
+        );
+    }
+    for m in moves(pages, sorted) {
+        println!("  move {} from {} to {}", m.page, m.from, m.to);
+    }
+}
+
+/// What the rule set implies as a whole
+#[derive(Debug)]
+struct Analysis {
+    /// The rules plus all rules implied by chains of them
+    closure: Rules,
+    /// Rules that are implied by a chain of other rules
+    redundant: Vec<(u32, u32)>,
+    /// Pages of updates that don't appear in any rule
+    unconstrained: BTreeSet<u32>,
+    /// A cycle in the rules between all pages, if there is one
+    cycle: Option<Vec<u32>>,
+    /// Updates whose own pages have a cycle in the rules between them
+    cyclic_updates: Vec<usize>,
+}
+
+fn rule_graph(rules: &Rules) -> Graph<u32> {
+    let mut graph = Graph::directed();
+    for (&before, after) in rules {
+        for &after in after {
+            graph.add_edge(before, after);
+        }
+    }
+    graph
+}
+
+fn analyze(rules: &Rules, updates: &[Vec<u32>]) -> Analysis {
+    let graph = rule_graph(rules);
+
+    let mut closure: Rules = BTreeMap::new();
+    for (node, reachable) in graph.transitive_closure().into_iter().enumerate() {
+        if !reachable.is_empty() {
+            closure.insert(
+                *graph.name(node),
+                reachable.into_iter().map(|n| *graph.name(n)).collect(),
+            );
+        }
+    }
+
+    Analysis {
+        closure,
+        redundant: graph
+            .redundant_edges()
+            .into_iter()
+            .map(|(from, to)| (*graph.name(from), *graph.name(to)))
+            .collect(),
+        unconstrained: updates
+            .iter()
+            .flatten()
+            .filter(|page| graph.index_of(page).is_none())
+            .copied()
+            .collect(),
+        cycle: graph
+            .toposort()
+            .err()
+            .map(|cycle| cycle.into_iter().map(|n| *graph.name(n)).collect()),
+        cyclic_updates: (0..updates.len())
+            .filter(|&i| matches!(order(rules, &updates[i]), UpdateOrder::Cycle(_)))
+            .collect(),
+    }
+}
+
+/// The number of rules in the closure that aren't given. Pages on a cycle come before
+/// themselves in the closure, but `p|p` doesn't count as a rule.
+fn implied_rules(rules: &Rules, closure: &Rules) -> usize {
+    closure
+        .iter()
+        .flat_map(|(before, afters)| afters.iter().map(move |after| (before, after)))
+        .filter(|&(before, after)| {
+            before != after && !rules.get(before).is_some_and(|r| r.contains(after))
+        })
+        .count()
+}
+
+fn print_analysis(rules: &Rules, analysis: &Analysis) {
+    let count = |rules: &Rules| rules.values().map(|r| r.len()).sum::<usize>();
+    println!("Rules: {}", count(rules));
+    println!("Implied rules: {}", implied_rules(rules, &analysis.closure));
+    println!("Redundant rules: {}", analysis.redundant.len());
+    for (before, after) in &analysis.redundant {
+        println!("  {}|{}", before, after);
+    }
+    println!("Unconstrained pages: {}", analysis.unconstrained.len());
+    for page in &analysis.unconstrained {
+        println!("  {}", page);
+    }
+    match (&analysis.cycle, analysis.cyclic_updates.len()) {
+        (None, _) => println!("Rule graph: DAG"),
+        (Some(_), 0) => println!("Rule graph: acyclic within each update"),
+        (Some(_), n) => println!("Rule graph: cyclic within {} updates", n),
+    }
+    if let Some(cycle) = &analysis.cycle {
+        let mut cycle = cycle.clone();
+        cycle.push(cycle[0]);
+        println!("  cycle {}", join(&cycle, " -> "));
+    }
+}
+
+fn join(pages: &[u32], separator: &str) -> String {
+    let pages: Vec<String> = pages.iter().map(|p| p.to_string()).collect();
+    pages.join(separator)
+}
+
+fn main() {
+    let mut show_explanation = false;
+    let mut show_analysis = false;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--explain" => show_explanation = true,
+            "--analyze" => show_analysis = true,
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let start = Instant::now();
+
+    let (rules, updates) = parse(INPUT);
+
+    if show_analysis {
+        print_analysis(&rules, &analyze(&rules, &updates));
+    }
+
+    // Sort the incorrect updates, then find their middle pages
+    let mut result = 0;
+    for (i, pages) in updates.iter().enumerate() {
+        if is_correct(&rules, pages) {
+            continue;
+        }
+        let sorted = match order(&rules, pages) {
+            UpdateOrder::Unique(sorted) => sorted,
+            UpdateOrder::Ambiguous(sorted) => {
+                eprintln!(
+                    "Update {} has no unique order, using {}",
+                    i + 1,
+                    join(&sorted, ",")
+                );
+                sorted
+            }
+            UpdateOrder::Cycle(mut cycle) => {
+                cycle.push(cycle[0]);
+                eprintln!(
+                    "Update {} can't be sorted, the rules form a cycle: {}",
+                    i + 1,
+                    join(&cycle, " -> ")
+                );
+                continue;
+            }
+        };
+        if show_explanation {
+            println!(
+                "Update {} ({}) -> {}",
+                i + 1,
+                join(pages, ","),
+                join(&sorted, ",")
+            );
+            explain(&rules, pages, &sorted);
+        }
+        result += sorted[sorted.len() / 2];
+    }
+
+    let elapsed = start.elapsed();
+
+    println!("Result: {}", result);
+    println!("(took: {:?})", elapsed);
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+
+#[test]
+fn test_order() {
+    let (rules, updates) = parse(EXAMPLE);
+    let correct: Vec<bool> = updates.iter().map(|u| is_correct(&rules, u)).collect();
+    assert_eq!(correct, vec![true, true, true, false, false, false]);
+    assert_eq!(
+        order(&rules, &updates[5]),
+        UpdateOrder::Unique(vec![97, 75, 47, 29, 13])
+    );
+
+    let (rules, _) = parse("1|2\n1|3\n3|4\n4|1\n");
+    assert_eq!(
+        order(&rules, &[3, 2, 1]),
+        UpdateOrder::Ambiguous(vec![1, 3, 2])
+    );
+    assert_eq!(
+        order(&rules, &[2, 4, 3, 1]),
+        UpdateOrder::Cycle(vec![3, 4, 1])
+    );
+}
+
+#[test]
+fn test_explain() {
+    let (rules, updates) = parse(EXAMPLE);
+    assert_eq!(
+        violations(&rules, &updates[4]),
+        vec![Violation {
+            before: 29,
+            after: 13,
+            before_position: 2,
+            after_position: 1
+        }]
+    );
+    assert_eq!(violations(&rules, &updates[5]).len(), 4);
+    assert!(violations(&rules, &updates[0]).is_empty());
+
+    // 97,13,75,29,47 -> 97,75,47,29,13, keeping 97,75,47 in place
+    assert_eq!(
+        moves(&updates[5], &[97, 75, 47, 29, 13]),
+        vec![
+            Move {
+                page: 13,
+                from: 1,
+                to: 4
+            },
+            Move {
+                page: 29,
+                from: 3,
+                to: 3
+            }
+        ]
+    );
+    assert!(moves(&[1, 2, 3], &[1, 2, 3]).is_empty());
+    assert_eq!(moves(&[3, 2, 1], &[1, 2, 3]).len(), 2);
+}
+
+#[test]
+fn test_analyze() {
+    let (rules, updates) = parse(EXAMPLE);
+    let analysis = analyze(&rules, &updates);
+    // The example rules are a total order of all pages
+    assert_eq!(analysis.closure, rules);
+    assert_eq!(implied_rules(&rules, &analysis.closure), 0);
+    assert_eq!(analysis.redundant.len(), 21 - 6);
+    assert!(analysis.unconstrained.is_empty());
+    assert_eq!(analysis.cycle, None);
+
+    let (rules, updates) = parse("1|2\n2|3\n3|1\n1|4\n\n1,2,5\n3,1,2");
+    let analysis = analyze(&rules, &updates);
+    assert_eq!(analysis.closure[&2], BTreeSet::from([1, 2, 3, 4]));
+    // 1, 2 and 3 each come before the two others and 4, without the 4 given rules
+    assert_eq!(implied_rules(&rules, &analysis.closure), 9 - 4);
+    assert_eq!(analysis.redundant, vec![]);
+    assert_eq!(analysis.unconstrained, BTreeSet::from([5]));
+    assert_eq!(analysis.cycle, Some(vec![2, 3, 1]));
+    assert_eq!(analysis.cyclic_updates, vec![1]);
+}
@@ -0,0 +1,103 @@
+use aoc_utils::grid::BitGrid;
+use rustc_hash::FxHashSet;
+
+use crate::DIRECTIONS;
+
+/// The guard leaves the map
+const EXIT: u32 = u32::MAX;
+
+/// For every cell and direction, where the guard stops when walking straight: the last cell
+/// before the next obstacle. The guard can then walk from turn to turn instead of cell by cell.
+pub struct JumpTable {
+    width: usize,
+    /// Cell index of the stop for each direction, or `EXIT`
+    stops: Vec<[u32; 4]>,
+}
+
+impl JumpTable {
+    pub fn new(obstacles: &BitGrid) -> Self {
+        let (width, height) = (obstacles.width(), obstacles.height());
+        let mut stops = vec![[EXIT; 4]; width * height];
+
+        for (dir_index, &(dx, dy)) in DIRECTIONS.iter().enumerate() {
+            // Visit the cells against the walking direction, so the next cell is already done
+            let xs: Vec<usize> = if dx > 0 {
+                (0..width).rev().collect()
+            } else {
+                (0..width).collect()
+            };
+            let ys: Vec<usize> = if dy > 0 {
+                (0..height).rev().collect()
+            } else {
+                (0..height).collect()
+            };
+            for &y in &ys {
+                for &x in &xs {
+                    let (nx, ny) = (x as i64 + dx as i64, y as i64 + dy as i64);
+                    stops[y * width + x][dir_index] = if !obstacles.contains(nx, ny) {
+                        EXIT
+                    } else if obstacles.get(nx as usize, ny as usize) {
+                        (y * width + x) as u32
+                    } else {
+                        stops[ny as usize * width + nx as usize][dir_index]
+                    };
+                }
+            }
+        }
+
+        JumpTable { width, stops }
+    }
+
+    fn position(&self, index: u32) -> (i32, i32) {
+        let index = index as usize;
+        ((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    /// Whether the guard walks in a loop if an obstacle is added at `obstacle_pos`, which
+    /// has to be a free cell. The jumps don't know about the new obstacle, so it's checked
+    /// on every straight walk.
+    pub fn loops(
+        &self,
+        obstacle_pos: (i32, i32),
+        mut guard_pos: (i32, i32),
+        mut guard_dir_index: usize,
+    ) -> bool {
+        // Only turns need to be remembered, every loop contains at least one
+        let mut turns: FxHashSet<((i32, i32), usize)> = FxHashSet::default();
+
+        loop {
+            let (dx, dy) = DIRECTIONS[guard_dir_index];
+            let index = guard_pos.1 as usize * self.width + guard_pos.0 as usize;
+            let stop = self.stops[index][guard_dir_index];
+
+            // Steps to the new obstacle, if it's straight ahead
+            let to_obstacle = if dx == 0 && obstacle_pos.0 == guard_pos.0 {
+                (obstacle_pos.1 - guard_pos.1) * dy
+            } else if dy == 0 && obstacle_pos.1 == guard_pos.1 {
+                (obstacle_pos.0 - guard_pos.0) * dx
+            } else {
+                0
+            };
+            let to_stop = if stop == EXIT {
+                i32::MAX
+            } else {
+                let (x, y) = self.position(stop);
+                (x - guard_pos.0).abs() + (y - guard_pos.1).abs()
+            };
+
+            guard_pos = if to_obstacle > 0 && to_obstacle <= to_stop {
+                (obstacle_pos.0 - dx, obstacle_pos.1 - dy)
+            } else if stop == EXIT {
+                return false;
+            } else {
+                self.position(stop)
+            };
+
+            // turn right
+            guard_dir_index = (guard_dir_index + 1) % DIRECTIONS.len();
+            if !turns.insert((guard_pos, guard_dir_index)) {
+                return true;
+            }
+        }
+    }
+}
@@ -0,0 +1,448 @@
+use std::time::Instant;
+
+use aoc_utils::grid::BitGrid;
+use jump::JumpTable;
+use patrol::{patrol, render};
+use rayon::prelude::*;
+use sim::{simulate, Edges, Movement, Outcome, Rules, Turn};
+
+mod jump;
+mod patrol;
+mod sim;
+
+const INPUT: &'static str = include_str!("input.txt");
+
+const USAGE: &str =
+    "Usage: day06b [--naive] [--path] [--obstacles] [--show-loop <n>] [--loops-to <file>]
+              [--simulate] [--turn <right|left|alternating>] [--eight-way] [--toroidal]
+
+Counts the positions where a new obstacle makes the guard walk in a loop. --naive uses the
+slow step by step simulation instead of the jump table.
+
+--path draws the patrol of the guard, --obstacles lists the positions that cause a loop.
+--show-loop draws the loop caused by the n-th of them, --loops-to writes all loops to a file.
+
+--simulate lets every guard on the map patrol and reports how each of them ends. The guards
+don't interact, they walk through each other and never block one another. The rules can be
+changed with --turn <right|left|alternating>, --eight-way (diagonal movement, turning by 45
+degrees) and --toroidal (guards wrap around the edges).";
+
+// X, Y
+const DIRECTIONS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// A grid of any size, stored on the heap
+#[derive(Clone)]
+struct Field<T> {
+    width: usize,
+    data: Vec<T>,
+}
+
+impl<T: Default + Copy> Field<T> {
+    pub fn new(width: usize, height: usize) -> Self {
+        Field {
+            width,
+            data: vec![Default::default(); width * height],
+        }
+    }
+
+    pub fn set(&mut self, row: i32, col: i32, value: T) {
+        self.data[row as usize * self.width + col as usize] = value;
+    }
+
+    pub fn get(&self, row: i32, col: i32) -> T {
+        self.data[row as usize * self.width + col as usize]
+    }
+}
+
+struct Map {
+    obstacles: BitGrid,
+    guard_pos: (i32, i32),
+    guard_dir_index: usize,
+    /// Position and direction of all guards, the first one is `guard_pos`
+    guards: Vec<((i32, i32), usize)>,
+}
+
+fn parse(input: &str) -> Result<Map, String> {
+    let lines: Vec<&str> = input.lines().filter(|l| !l.is_empty()).collect();
+    let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+
+    let mut obstacles = BitGrid::new(width, lines.len());
+    let mut guards = vec![];
+
+    for (row, line) in lines.iter().enumerate() {
+        for (col, char) in line.chars().enumerate() {
+            let guard_pos = (col as i32, row as i32);
+            if char == '#' {
+                obstacles.set(col, row, true);
+            } else if char == '^' {
+                guards.push((guard_pos, 0));
+            } else if char == 'v' {
+                guards.push((guard_pos, 2));
+            } else if char == '<' {
+                guards.push((guard_pos, 3));
+            } else if char == '>' {
+                guards.push((guard_pos, 1));
+            }
+        }
+    }
+
+    let &(guard_pos, guard_dir_index) = guards.first().ok_or("There is no guard on the map")?;
+    Ok(Map {
+        obstacles,
+        guard_pos,
+        guard_dir_index,
+        guards,
+    })
+}
+
+/// The positions where a new obstacle makes the guard walk in a loop, in the order the guard
+/// reaches them, by simulating every candidate step by step
+fn loop_obstacles_naive(map: &Map) -> Vec<(i32, i32)> {
+    let guard = (map.guard_pos, map.guard_dir_index);
+    candidates(map)
+        .into_iter()
+        .map(|c| c.obstacle_pos)
+        .filter(|&(x, y)| {
+            let mut obstacles = map.obstacles.clone();
+            obstacles.set(x as usize, y as usize, true);
+            simulate(&obstacles, &[guard], &Rules::default()).guards[0].outcome == Outcome::Looped
+        })
+        .collect()
+}
+
+/// A cell on the guard's path where a new obstacle could go
+struct Candidate {
+    obstacle_pos: (i32, i32),
+    /// The guard right before it reaches the cell for the first time
+    guard_pos: (i32, i32),
+    guard_dir_index: usize,
+}
+
+fn candidates(map: &Map) -> Vec<Candidate> {
+    let Map {
+        obstacles,
+        mut guard_pos,
+        mut guard_dir_index,
+        ..
+    } = map;
+
+    let mut visited = BitGrid::new(obstacles.width(), obstacles.height());
+    visited.set(guard_pos.0 as usize, guard_pos.1 as usize, true);
+    let mut candidates = vec![];
+
+    loop {
+        let guard_dir = DIRECTIONS[guard_dir_index];
+        let next_pos = (guard_pos.0 + guard_dir.0, guard_pos.1 + guard_dir.1);
+        if !obstacles.contains(next_pos.0 as i64, next_pos.1 as i64) {
+            break;
+        }
+
+        let (x, y) = (next_pos.0 as usize, next_pos.1 as usize);
+        if obstacles.get(x, y) {
+            // turn right
+            guard_dir_index = (guard_dir_index + 1) % DIRECTIONS.len();
+        } else {
+            // An obstacle on a cell that was passed before would have changed the path earlier
+            if visited.insert(x, y) {
+                candidates.push(Candidate {
+                    obstacle_pos: next_pos,
+                    guard_pos,
+                    guard_dir_index,
+                });
+            }
+            guard_pos = next_pos;
+        }
+    }
+
+    candidates
+}
+
+/// The positions where a new obstacle makes the guard walk in a loop, in the order the guard
+/// reaches them. The guard jumps from turn to turn, starting right before the new obstacle,
+/// and the candidates are checked in parallel.
+fn loop_obstacles(map: &Map) -> Vec<(i32, i32)> {
+    let table = JumpTable::new(&map.obstacles);
+    candidates(map)
+        .into_par_iter()
+        .filter(|c| table.loops(c.obstacle_pos, c.guard_pos, c.guard_dir_index))
+        .map(|c| c.obstacle_pos)
+        .collect()
+}
+
+/// Draws the loop the guard walks in with a new obstacle
+fn render_loop(map: &Map, obstacle_pos: (i32, i32)) -> String {
+    let mut obstacles = map.obstacles.clone();
+    obstacles.set(obstacle_pos.0 as usize, obstacle_pos.1 as usize, true);
+    let patrol = patrol(&obstacles, map.guard_pos, map.guard_dir_index);
+    render(&obstacles, &patrol, Some(obstacle_pos))
+}
+
+fn write_loops(path: &str, map: &Map, obstacles: &[(i32, i32)]) -> std::io::Result<()> {
+    let mut out = String::new();
+    for (i, &(x, y)) in obstacles.iter().enumerate() {
+        out.push_str(&format!(
+            "Loop {} with an obstacle at ({}, {})\n",
+            i + 1,
+            x,
+            y
+        ));
+        out.push_str(&render_loop(map, (x, y)));
+        out.push('\n');
+    }
+    std::fs::write(path, out)
+}
+
+fn main() {
+    let mut naive = false;
+    let mut show_path = false;
+    let mut show_obstacles = false;
+    let mut show_loop: Option<usize> = None;
+    let mut loops_file: Option<String> = None;
+    let mut simulation: Option<Rules> = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--naive" => naive = true,
+            "--path" => show_path = true,
+            "--obstacles" => show_obstacles = true,
+            "--show-loop" => match args.next().and_then(|n| n.parse().ok()) {
+                Some(n) => show_loop = Some(n),
+                None => {
+                    eprintln!("{}", USAGE);
+                    std::process::exit(1);
+                }
+            },
+            "--loops-to" => match args.next() {
+                Some(path) => loops_file = Some(path),
+                None => {
+                    eprintln!("{}", USAGE);
+                    std::process::exit(1);
+                }
+            },
+            "--simulate" => {
+                simulation.get_or_insert_with(Rules::default);
+            }
+            "--turn" => {
+                simulation.get_or_insert_with(Rules::default).turn = match args.next().as_deref() {
+                    Some("right") => Turn::Right,
+                    Some("left") => Turn::Left,
+                    Some("alternating") => Turn::Alternating,
+                    _ => {
+                        eprintln!("{}", USAGE);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            "--eight-way" => {
+                simulation.get_or_insert_with(Rules::default).movement = Movement::EightWay;
+            }
+            "--toroidal" => simulation.get_or_insert_with(Rules::default).edges = Edges::Toroidal,
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let start = Instant::now();
+
+    let map = parse(INPUT).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+    let obstacles = if naive {
+        loop_obstacles_naive(&map)
+    } else {
+        loop_obstacles(&map)
+    };
+    let result = obstacles.len();
+
+    let elapsed = start.elapsed();
+
+    if show_path {
+        let patrol = patrol(&map.obstacles, map.guard_pos, map.guard_dir_index);
+        print!("{}", render(&map.obstacles, &patrol, None));
+    }
+    if show_obstacles {
+        for (i, (x, y)) in obstacles.iter().enumerate() {
+            println!("#{} at ({}, {})", i + 1, x, y);
+        }
+    }
+    if let Some(n) = show_loop {
+        match obstacles.get(n.wrapping_sub(1)) {
+            Some(&obstacle_pos) => print!("{}", render_loop(&map, obstacle_pos)),
+            None => eprintln!("There is no loop {}, only {}", n, obstacles.len()),
+        }
+    }
+    if let Some(path) = loops_file {
+        if let Err(e) = write_loops(&path, &map, &obstacles) {
+            eprintln!("Could not write the loops to {}: {}", path, e);
+        }
+    }
+
+    if let Some(rules) = simulation {
+        let report = simulate(&map.obstacles, &map.guards, &rules);
+        for (guard, (pos, _)) in report.guards.iter().zip(&map.guards) {
+            let outcome = match guard.outcome {
+                Outcome::Exited => "leaves the map",
+                Outcome::Looped => "walks in a loop",
+            };
+            println!(
+                "Guard at ({}, {}) {} after {} steps, visiting {} cells",
+                pos.0, pos.1, outcome, guard.steps, guard.visited
+            );
+        }
+        let count = |outcome| {
+            report
+                .guards
+                .iter()
+                .filter(|g| g.outcome == outcome)
+                .count()
+        };
+        println!("Exited: {}", count(Outcome::Exited));
+        println!("Looped: {}", count(Outcome::Looped));
+        println!("Visited: {}", report.visited);
+    }
+
+    println!("Result: {}", result);
+    println!("(took: {:?})", elapsed);
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+#[test]
+fn test_example() {
+    let map = parse(EXAMPLE).unwrap();
+    assert_eq!((map.obstacles.width(), map.obstacles.height()), (10, 10));
+    assert_eq!(
+        loop_obstacles(&map),
+        vec![(3, 6), (6, 7), (3, 8), (1, 8), (7, 7), (7, 9)]
+    );
+    assert_eq!(loop_obstacles_naive(&map), loop_obstacles(&map));
+
+    // Not square, only an obstacle in front of the exit closes the loop
+    let map = parse(".#...\n....#\n.^...\n...#.").unwrap();
+    assert_eq!((map.obstacles.width(), map.obstacles.height()), (5, 4));
+    assert_eq!(loop_obstacles(&map), vec![(0, 2)]);
+    assert_eq!(loop_obstacles_naive(&map), vec![(0, 2)]);
+
+    assert!(parse("..#\n...").is_err());
+}
+
+#[test]
+fn test_render() {
+    let map = parse(EXAMPLE).unwrap();
+    let patrol = patrol(&map.obstacles, map.guard_pos, map.guard_dir_index);
+    assert_eq!(
+        render(&map.obstacles, &patrol, None),
+        "....#.....
+....+>>>+#
+....^...v.
+..#.^...v.
+..+>+>+#v.
+..^.^.v.v.
+.#+<+<+<+.
+.+>>>>++#.
+#+<<<<+v..
+......#v..
+"
+    );
+
+    assert_eq!(
+        render_loop(&map, (3, 6)),
+        "....#.....
+....+>>>+#
+....^...v.
+..#.^...v.
+....^..#v.
+....^...v.
+.#.O+<<<+.
+........#.
+#.........
+......#...
+"
+    );
+}
+
+#[test]
+fn test_jump_table() {
+    let map = parse(EXAMPLE).unwrap();
+    let table = JumpTable::new(&map.obstacles);
+    // The obstacles from the puzzle description
+    for obstacle_pos in [(3, 6), (6, 7), (7, 7), (1, 8), (3, 8), (7, 9)] {
+        assert!(table.loops(obstacle_pos, (4, 6), 0));
+    }
+    assert!(!table.loops((4, 5), (4, 6), 0));
+    assert!(!table.loops((0, 0), (4, 6), 0));
+}
+
+#[test]
+fn test_simulate() {
+    use sim::GuardReport;
+
+    let map = parse(EXAMPLE).unwrap();
+    let report = simulate(&map.obstacles, &map.guards, &Rules::default());
+    assert_eq!(report.visited, 41);
+    assert_eq!(report.guards[0].outcome, Outcome::Exited);
+
+    let rules = Rules {
+        turn: Turn::Left,
+        ..Rules::default()
+    };
+    let report = simulate(&map.obstacles, &map.guards, &rules);
+    assert_eq!(report.visited, 10);
+    assert_eq!(report.guards[0].outcome, Outcome::Exited);
+
+    // Without edges, the guard can only end in a loop
+    let rules = Rules {
+        edges: Edges::Toroidal,
+        ..Rules::default()
+    };
+    let report = simulate(&map.obstacles, &map.guards, &rules);
+    assert_eq!(report.guards[0].outcome, Outcome::Looped);
+
+    // Two guards in the same room, one bouncing diagonally, one leaving right away
+    let map = parse("#####\n#...#\n#>..#\n#####\n....<").unwrap();
+    assert_eq!(map.guards, vec![((1, 2), 1), ((4, 4), 3)]);
+    let rules = Rules {
+        movement: Movement::EightWay,
+        ..Rules::default()
+    };
+    let report = simulate(&map.obstacles, &map.guards, &rules);
+    assert_eq!(report.guards[0].outcome, Outcome::Looped);
+    assert_eq!(
+        report.guards[1],
+        GuardReport {
+            outcome: Outcome::Exited,
+            steps: 4,
+            visited: 5
+        }
+    );
+    assert_eq!(report.visited, 6 + 5);
+
+    // Turning right and then left, the guard zigzags up and out at the top
+    let map = parse(".#..\n...#\n.^..").unwrap();
+    let rules = Rules {
+        turn: Turn::Alternating,
+        ..Rules::default()
+    };
+    let report = simulate(&map.obstacles, &map.guards, &rules);
+    assert_eq!(
+        report.guards[0],
+        GuardReport {
+            outcome: Outcome::Exited,
+            steps: 5,
+            visited: 4
+        }
+    );
+}
@@ -0,0 +1,49 @@
+use aoc_utils::grid::BitGrid;
+
+use crate::sim::{trace, Rules, Trace};
+
+/// Indexed by the orthogonal directions in `DIRECTIONS_8`, which are every second one
+const ARROWS: [char; 4] = ['^', '>', 'v', '<'];
+const VERTICAL: u8 = 0b0001_0001;
+const HORIZONTAL: u8 = 0b0100_0100;
+
+/// The path of the guard from the puzzle
+pub struct Patrol {
+    trace: Trace,
+}
+
+pub fn patrol(obstacles: &BitGrid, guard_pos: (i32, i32), guard_dir_index: usize) -> Patrol {
+    let (_, trace) = trace(obstacles, (guard_pos, guard_dir_index), &Rules::default());
+    Patrol { trace }
+}
+
+/// Draws the map with the path of the guard: arrows where it walked in one direction, `|` and
+/// `-` where it walked back and forth, and `+` where it turned or crossed its own path.
+/// `new_obstacle` is drawn as `O`.
+pub fn render(obstacles: &BitGrid, patrol: &Patrol, new_obstacle: Option<(i32, i32)>) -> String {
+    let mut out = String::new();
+    for y in 0..obstacles.height() {
+        for x in 0..obstacles.width() {
+            let directions = patrol.trace.directions.get(y as i32, x as i32);
+            let vertical = directions & VERTICAL != 0;
+            let horizontal = directions & HORIZONTAL != 0;
+            out.push(if new_obstacle == Some((x as i32, y as i32)) {
+                'O'
+            } else if obstacles.get(x, y) {
+                '#'
+            } else if patrol.trace.turns.get(x, y) || (vertical && horizontal) {
+                '+'
+            } else if directions.count_ones() == 1 {
+                ARROWS[directions.trailing_zeros() as usize / 2]
+            } else if vertical {
+                '|'
+            } else if horizontal {
+                '-'
+            } else {
+                '.'
+            });
+        }
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,255 @@
+use aoc_utils::grid::BitGrid;
+
+use crate::Field;
+
+/// Clockwise, starting with up. Orthogonal guards only use every second one.
+const DIRECTIONS_8: [(i32, i32); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turn {
+    Right,
+    Left,
+    /// Right first, then left, then right again, ...
+    Alternating,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Movement {
+    /// Up, right, down, left, turning by 90 degrees
+    Orthogonal,
+    /// Diagonals as well, turning by 45 degrees
+    EightWay,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edges {
+    /// The guard leaves the map
+    Exit,
+    /// The guard continues on the opposite edge
+    Toroidal,
+}
+
+/// How the guards move. The default is the guard from the puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    pub turn: Turn,
+    pub movement: Movement,
+    pub edges: Edges,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            turn: Turn::Right,
+            movement: Movement::Orthogonal,
+            edges: Edges::Exit,
+        }
+    }
+}
+
+impl Rules {
+    /// Steps through `DIRECTIONS_8` per turn
+    fn turn_step(&self) -> usize {
+        match self.movement {
+            Movement::Orthogonal => 2,
+            Movement::EightWay => 1,
+        }
+    }
+
+    /// The direction after the guard's `turns`-th turn
+    fn turn(&self, dir_index: usize, turns: usize) -> usize {
+        let right = match self.turn {
+            Turn::Right => true,
+            Turn::Left => false,
+            Turn::Alternating => turns.is_multiple_of(2),
+        };
+        let step = if right {
+            self.turn_step()
+        } else {
+            DIRECTIONS_8.len() - self.turn_step()
+        };
+        (dir_index + step) % DIRECTIONS_8.len()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The guard left the map
+    Exited,
+    /// The guard came back to a position and direction it had before
+    Looped,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardReport {
+    pub outcome: Outcome,
+    /// Moves and turns until the guard left or started repeating itself
+    pub steps: usize,
+    /// Distinct cells the guard stood on
+    pub visited: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub guards: Vec<GuardReport>,
+    /// Distinct cells any of the guards stood on
+    pub visited: usize,
+}
+
+/// The path of a guard, as much as is needed to draw it
+pub struct Trace {
+    /// The directions the guard left each cell in, as bits `1 << dir_index` into `DIRECTIONS_8`
+    pub directions: Field<u8>,
+    /// Cells where the guard turned
+    pub turns: BitGrid,
+}
+
+struct Guard {
+    pos: (i32, i32),
+    /// Index into `DIRECTIONS_8`
+    dir_index: usize,
+    turns: usize,
+    steps: usize,
+    /// The states the guard was in before, as bits `1 << (dir_index * 2 + parity)`
+    states: Field<u16>,
+    visited: BitGrid,
+    outcome: Option<Outcome>,
+    trace: Option<Trace>,
+}
+
+impl Guard {
+    /// Remembers that the guard leaves its cell in its current direction
+    fn record_move(&mut self) {
+        if let Some(trace) = &mut self.trace {
+            let directions = trace.directions.get(self.pos.1, self.pos.0);
+            let direction = 1 << self.dir_index;
+            trace
+                .directions
+                .set(self.pos.1, self.pos.0, directions | direction);
+        }
+    }
+
+    fn record_turn(&mut self) {
+        if let Some(trace) = &mut self.trace {
+            trace
+                .turns
+                .set(self.pos.0 as usize, self.pos.1 as usize, true);
+        }
+    }
+}
+
+/// Moves all guards at the same time, one move or turn per step, until each of them has left
+/// the map or is walking in a loop. The guards don't interact: they can share a cell and never
+/// block or collide with each other, so each of them ends as if it was alone on the map.
+/// Their directions are indices into the 4 orthogonal `DIRECTIONS`.
+pub fn simulate(obstacles: &BitGrid, guards: &[((i32, i32), usize)], rules: &Rules) -> Report {
+    run(obstacles, guards, rules, false).0
+}
+
+/// Simulates a single guard and records its path
+pub fn trace(
+    obstacles: &BitGrid,
+    guard: ((i32, i32), usize),
+    rules: &Rules,
+) -> (GuardReport, Trace) {
+    let (report, traces) = run(obstacles, &[guard], rules, true);
+    let report = report.guards.into_iter().next().unwrap();
+    (report, traces.into_iter().next().flatten().unwrap())
+}
+
+fn run(
+    obstacles: &BitGrid,
+    guards: &[((i32, i32), usize)],
+    rules: &Rules,
+    trace: bool,
+) -> (Report, Vec<Option<Trace>>) {
+    let (width, height) = (obstacles.width(), obstacles.height());
+    let mut visited = BitGrid::new(width, height);
+    let mut guards: Vec<Guard> = guards
+        .iter()
+        .map(|&(pos, dir_index)| {
+            let mut guard_visited = BitGrid::new(width, height);
+            guard_visited.set(pos.0 as usize, pos.1 as usize, true);
+            visited.set(pos.0 as usize, pos.1 as usize, true);
+            Guard {
+                pos,
+                dir_index: dir_index * 2,
+                turns: 0,
+                steps: 0,
+                states: Field::new(width, height),
+                visited: guard_visited,
+                outcome: None,
+                trace: trace.then(|| Trace {
+                    directions: Field::new(width, height),
+                    turns: BitGrid::new(width, height),
+                }),
+            }
+        })
+        .collect();
+
+    while guards.iter().any(|g| g.outcome.is_none()) {
+        for guard in guards.iter_mut().filter(|g| g.outcome.is_none()) {
+            // Only alternating guards can face the same way on the same cell and still turn
+            // differently at the next obstacle
+            let parity = match rules.turn {
+                Turn::Alternating => guard.turns % 2,
+                Turn::Right | Turn::Left => 0,
+            };
+            let state = 1 << (guard.dir_index * 2 + parity);
+            let states = guard.states.get(guard.pos.1, guard.pos.0);
+            if states & state != 0 {
+                guard.outcome = Some(Outcome::Looped);
+                continue;
+            }
+            guard.states.set(guard.pos.1, guard.pos.0, states | state);
+
+            let (dx, dy) = DIRECTIONS_8[guard.dir_index];
+            let mut next_pos = (guard.pos.0 + dx, guard.pos.1 + dy);
+            if rules.edges == Edges::Toroidal {
+                next_pos = (
+                    next_pos.0.rem_euclid(width as i32),
+                    next_pos.1.rem_euclid(height as i32),
+                );
+            } else if !obstacles.contains(next_pos.0 as i64, next_pos.1 as i64) {
+                guard.record_move();
+                guard.outcome = Some(Outcome::Exited);
+                continue;
+            }
+
+            guard.steps += 1;
+            let (x, y) = (next_pos.0 as usize, next_pos.1 as usize);
+            if obstacles.get(x, y) {
+                guard.record_turn();
+                guard.dir_index = rules.turn(guard.dir_index, guard.turns);
+                guard.turns += 1;
+            } else {
+                guard.record_move();
+                guard.pos = next_pos;
+                guard.visited.set(x, y, true);
+                visited.set(x, y, true);
+            }
+        }
+    }
+
+    let report = Report {
+        guards: guards
+            .iter()
+            .map(|g| GuardReport {
+                outcome: g.outcome.unwrap(),
+                steps: g.steps,
+                visited: g.visited.count_ones(),
+            })
+            .collect(),
+        visited: visited.count_ones(),
+    };
+    (report, guards.into_iter().map(|g| g.trace).collect())
+}
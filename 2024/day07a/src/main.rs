@@ -0,0 +1,215 @@
+use std::{fmt::Display, time::Instant};
+
+const INPUT: &'static str = include_str!("input.txt");
+
+const USAGE: &str = "Usage: day07a [--expressions] [--all]
+
+Sums the test values of the equations that can be made true with + and *.
+--expressions prints the first operators found for each of them, --all prints every
+combination of operators that works and how many there are.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Multiply,
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operator::Add => write!(f, "+"),
+            Operator::Multiply => write!(f, "*"),
+        }
+    }
+}
+
+fn parse_line(line: &str) -> (u64, Vec<u64>) {
+    let (test_val, numbers) = line.split_once(": ").unwrap();
+    let test_val = test_val.parse::<u64>().unwrap();
+    let numbers = numbers
+        .split_whitespace()
+        .map(|num| num.parse::<u64>().unwrap())
+        .collect::<Vec<u64>>();
+    (test_val, numbers)
+}
+
+/// The equation with the operators between the numbers, e.g. `190 = 10 * 19`
+fn expression(test_val: u64, numbers: &[u64], operators: &[Operator]) -> String {
+    let mut out = format!("{} = {}", test_val, numbers[0]);
+    for (operator, number) in operators.iter().zip(&numbers[1..]) {
+        out.push_str(&format!(" {} {}", operator, number));
+    }
+    out
+}
+
+fn main() {
+    let mut show_expressions = false;
+    let mut show_all = false;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--expressions" => show_expressions = true,
+            "--all" => show_all = true,
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let start = Instant::now();
+
+    let equations: Vec<(u64, Vec<u64>)> = INPUT.lines().map(parse_line).collect();
+    let solved: Vec<(u64, &[u64], Vec<Operator>)> = equations
+        .iter()
+        .filter_map(|(test_val, numbers)| {
+            test(*test_val, numbers).map(|operators| (*test_val, &numbers[..], operators))
+        })
+        .collect();
+    let result: u64 = solved.iter().map(|(test_val, _, _)| test_val).sum();
+
+    let elapsed = start.elapsed();
+
+    if show_expressions {
+        for (test_val, numbers, operators) in &solved {
+            println!("{}", expression(*test_val, numbers, operators));
+        }
+    }
+    if show_all {
+        let mut count = 0;
+        for (test_val, numbers, _) in &solved {
+            for operators in solutions(*test_val, numbers) {
+                println!("{}", expression(*test_val, numbers, &operators));
+                count += 1;
+            }
+        }
+        println!("Solutions: {}", count);
+    }
+
+    println!("Result: {}", result);
+    println!("(took: {:?})", elapsed);
+}
+
+/// Searches backwards from the test value, undoing the last operator first.
+/// Calls `found` with the operators of every solution, and stops once it returns `true`.
+fn search(
+    test_val: u64,
+    numbers: &[u64],
+    operators: &mut Vec<Operator>,
+    found: &mut impl FnMut(&[Operator]) -> bool,
+) -> bool {
+    match numbers {
+        [last] => *last == test_val && found(operators),
+        [rest @ .., last] => {
+            // Multiplying by 0 gives 0 from any left side
+            if *last == 0 && test_val == 0 {
+                operators.push(Operator::Multiply);
+                let done = search_any(rest[0], &rest[1..], &mut vec![], operators, found);
+                operators.pop();
+                if done {
+                    return true;
+                }
+            }
+            let mut undo = |operator, test_val| {
+                operators.push(operator);
+                let done = search(test_val, rest, operators, found);
+                operators.pop();
+                done
+            };
+            // Test multiplication
+            if *last != 0
+                && test_val.is_multiple_of(*last)
+                && undo(Operator::Multiply, test_val / last)
+            {
+                return true;
+            }
+            // Test addition
+            if test_val >= *last && undo(Operator::Add, test_val - last) {
+                return true;
+            }
+
+            false
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Like `search`, but accepts any operators that can be evaluated from `value` over `numbers`
+/// without overflowing. `prefix` are the operators from the first number to `value`.
+fn search_any(
+    value: u64,
+    numbers: &[u64],
+    prefix: &mut Vec<Operator>,
+    operators: &mut Vec<Operator>,
+    found: &mut impl FnMut(&[Operator]) -> bool,
+) -> bool {
+    let Some((&right, rest)) = numbers.split_first() else {
+        // `operators` go from the last number to the first
+        let len = operators.len();
+        operators.extend(prefix.iter().rev());
+        let done = found(operators);
+        operators.truncate(len);
+        return done;
+    };
+    let values = [
+        (Operator::Multiply, value.checked_mul(right)),
+        (Operator::Add, value.checked_add(right)),
+    ];
+    for (operator, value) in values {
+        if let Some(value) = value {
+            prefix.push(operator);
+            let done = search_any(value, rest, prefix, operators, found);
+            prefix.pop();
+            if done {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// The operators between the numbers that make the equation true, if there are any
+fn test(test_val: u64, numbers: &[u64]) -> Option<Vec<Operator>> {
+    let mut solution = None;
+    search(test_val, numbers, &mut vec![], &mut |operators| {
+        solution = Some(operators.iter().rev().copied().collect());
+        true
+    });
+    solution
+}
+
+/// All combinations of operators that make the equation true
+fn solutions(test_val: u64, numbers: &[u64]) -> Vec<Vec<Operator>> {
+    let mut solutions = vec![];
+    search(test_val, numbers, &mut vec![], &mut |operators| {
+        solutions.push(operators.iter().rev().copied().collect());
+        false
+    });
+    solutions
+}
+
+#[test]
+fn test_expressions() {
+    use Operator::*;
+
+    let (test_val, numbers) = parse_line("190: 10 19");
+    let operators = test(test_val, &numbers).unwrap();
+    assert_eq!(operators, vec![Multiply]);
+    assert_eq!(expression(test_val, &numbers, &operators), "190 = 10 * 19");
+
+    assert_eq!(test(156, &[15, 6]), None);
+    let mut all = solutions(3267, &[81, 40, 27]);
+    all.sort_by_key(|ops| format!("{:?}", ops));
+    assert_eq!(all, vec![vec![Add, Multiply], vec![Multiply, Add]]);
+}
+
+#[test]
+fn test_multiply_by_zero() {
+    use Operator::*;
+
+    assert_eq!(test(0, &[5, 0]), Some(vec![Multiply]));
+    assert_eq!(test(1, &[5, 0]), None);
+    let mut all = solutions(0, &[2, 3, 0]);
+    all.sort_by_key(|ops| format!("{:?}", ops));
+    assert_eq!(all, vec![vec![Add, Multiply], vec![Multiply, Multiply]]);
+    assert_eq!(solutions(0, &[0, 0]).len(), 2);
+}
@@ -0,0 +1,419 @@
+use std::{ops::RangeInclusive, time::Instant};
+
+use num::BigUint;
+use number::{Number, Overflow};
+use operators::{parse_operator, Applied, Lefts, Operator};
+
+mod number;
+mod operators;
+
+const INPUT: &'static str = include_str!("input.txt");
+
+const USAGE: &str = "Usage: day07b [--operators <op,...>] [--u128 | --big] [--expressions] [--all]
+
+Sums the test values of the equations that can be made true with the operators, by default
+*, + and ||. Also available are -, / (rounding down), ^ (xor) and ||<base> (concatenation
+in another base). --expressions prints the first operators found for each equation,
+--all prints every combination of operators that works and how many there are.
+
+Numbers are u64, or u128 or arbitrarily large with --u128 and --big. Values that don't fit
+are reported instead of overflowing.";
+
+const DEFAULT_OPERATORS: &str = "*,+,||";
+
+fn parse_line<N: Number>(line: &str) -> Result<(N, Vec<N>), String> {
+    let parse = |num: &str| {
+        num.parse::<N>()
+            .map_err(|_| format!("{:?} is not a number that fits into {}", num, N::NAME))
+    };
+    let (test_val, numbers) = line
+        .split_once(": ")
+        .ok_or_else(|| format!("Expected an equation, got {:?}", line))?;
+    let test_val = parse(test_val)?;
+    let numbers = numbers
+        .split_whitespace()
+        .map(parse)
+        .collect::<Result<Vec<N>, _>>()?;
+    if numbers.is_empty() {
+        return Err(format!("No numbers in {:?}", line));
+    }
+    Ok((test_val, numbers))
+}
+
+/// Finds operators that make equations true. Solutions are the indices of the operators
+/// between the numbers.
+struct Solver<N> {
+    operators: Vec<Box<dyn Operator<N>>>,
+}
+
+impl<N: Number> Solver<N> {
+    fn parse(operators: &str) -> Result<Self, String> {
+        let operators = operators
+            .split(',')
+            .map(|symbol| {
+                parse_operator(symbol.trim())
+                    .ok_or_else(|| format!("Unknown operator {:?}", symbol))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Solver { operators })
+    }
+
+    /// The equation with the operators between the numbers, e.g. `156 = 15 || 6`
+    fn expression(&self, test_val: &N, numbers: &[N], operators: &[usize]) -> String {
+        let mut out = format!("{} = {}", test_val, numbers[0]);
+        for (&operator, number) in operators.iter().zip(&numbers[1..]) {
+            out.push_str(&format!(
+                " {} {}",
+                self.operators[operator].symbol(),
+                number
+            ));
+        }
+        out
+    }
+
+    /// Applies the operators left to right
+    fn evaluate(&self, numbers: &[N], operators: &[usize]) -> Result<N, Applied> {
+        operators
+            .iter()
+            .zip(&numbers[1..])
+            .try_fold(numbers[0].clone(), |left, (&operator, right)| {
+                self.operators[operator].apply(&left, right)
+            })
+    }
+
+    /// Searches backwards from the test value, undoing the last operator first. The values
+    /// the numbers before it have to evaluate to are a range, as there can be many of them
+    /// after undoing a division.
+    /// Calls `found` with the operators of every solution, and stops once it returns `true`.
+    /// Sets `overflow` if some branches couldn't be searched because their values don't fit.
+    fn search(
+        &self,
+        target: &RangeInclusive<N>,
+        numbers: &[N],
+        operators: &mut Vec<usize>,
+        overflow: &mut bool,
+        found: &mut impl FnMut(&[usize]) -> bool,
+    ) -> bool {
+        match numbers {
+            [first] => target.contains(first) && found(operators),
+            [rest @ .., last] => {
+                for (index, operator) in self.operators.iter().enumerate() {
+                    operators.push(index);
+                    let done = match operator.undo(target, last, overflow) {
+                        Lefts::None => false,
+                        Lefts::Range(range) => {
+                            self.search(&range, rest, operators, overflow, found)
+                        }
+                        Lefts::Ranges(ranges) => ranges
+                            .iter()
+                            .any(|range| self.search(range, rest, operators, overflow, found)),
+                        Lefts::Any => self.search_evaluable(
+                            &rest[0],
+                            &rest[1..],
+                            &mut vec![],
+                            operators,
+                            overflow,
+                            found,
+                        ),
+                    };
+                    operators.pop();
+                    if done {
+                        return true;
+                    }
+                }
+                false
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Like `search`, but accepts any operators that can be evaluated from `value` over
+    /// `numbers`, left to right. `prefix` are the operators from the first number to `value`.
+    /// Sets `overflow` if some operators couldn't be applied because their results don't fit.
+    fn search_evaluable(
+        &self,
+        value: &N,
+        numbers: &[N],
+        prefix: &mut Vec<usize>,
+        operators: &mut Vec<usize>,
+        overflow: &mut bool,
+        found: &mut impl FnMut(&[usize]) -> bool,
+    ) -> bool {
+        let Some((right, rest)) = numbers.split_first() else {
+            // `operators` go from the last number to the first
+            let len = operators.len();
+            operators.extend(prefix.iter().rev());
+            let done = found(operators);
+            operators.truncate(len);
+            return done;
+        };
+        for (index, operator) in self.operators.iter().enumerate() {
+            match operator.apply(value, right) {
+                Ok(value) => {
+                    prefix.push(index);
+                    let done =
+                        self.search_evaluable(&value, rest, prefix, operators, overflow, found);
+                    prefix.pop();
+                    if done {
+                        return true;
+                    }
+                }
+                Err(Applied::Overflow) => *overflow = true,
+                Err(Applied::Undefined) => {}
+            }
+        }
+        false
+    }
+
+    /// The operators between the numbers that make the equation true, if there are any.
+    /// Fails if there's no solution within `N`, but there might be one with larger values.
+    fn test(&self, test_val: &N, numbers: &[N]) -> Result<Option<Vec<usize>>, Overflow> {
+        let mut solution: Option<Vec<usize>> = None;
+        let mut overflow = false;
+        self.search(
+            &(test_val.clone()..=test_val.clone()),
+            numbers,
+            &mut vec![],
+            &mut overflow,
+            &mut |operators| {
+                solution = Some(operators.iter().rev().copied().collect());
+                true
+            },
+        );
+        if let Some(operators) = &solution {
+            debug_assert_eq!(self.evaluate(numbers, operators).as_ref(), Ok(test_val));
+        }
+        match solution {
+            None if overflow => Err(Overflow),
+            solution => Ok(solution),
+        }
+    }
+
+    /// All combinations of operators that make the equation true. Fails if there might be
+    /// more with values that don't fit into `N`.
+    fn solutions(&self, test_val: &N, numbers: &[N]) -> Result<Vec<Vec<usize>>, Overflow> {
+        let mut solutions = vec![];
+        let mut overflow = false;
+        self.search(
+            &(test_val.clone()..=test_val.clone()),
+            numbers,
+            &mut vec![],
+            &mut overflow,
+            &mut |operators| {
+                solutions.push(operators.iter().rev().copied().collect());
+                false
+            },
+        );
+        if overflow {
+            Err(Overflow)
+        } else {
+            Ok(solutions)
+        }
+    }
+}
+
+/// The type the numbers are parsed into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NumberType {
+    U64,
+    U128,
+    Big,
+}
+
+struct Options {
+    operators: String,
+    show_expressions: bool,
+    show_all: bool,
+}
+
+fn run<N: Number>(options: &Options) -> Result<(), String> {
+    let solver = Solver::<N>::parse(&options.operators)?;
+    let too_large = || format!("Values don't fit into {}, try --u128 or --big", N::NAME);
+
+    let start = Instant::now();
+
+    let equations = INPUT
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_line::<N>(line).map_err(|e| format!("Line {}: {}", i + 1, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut solved: Vec<(&N, &[N], Vec<usize>)> = vec![];
+    for (i, (test_val, numbers)) in equations.iter().enumerate() {
+        match solver.test(test_val, numbers) {
+            Ok(Some(operators)) => solved.push((test_val, numbers, operators)),
+            Ok(None) => {}
+            Err(Overflow) => return Err(format!("Line {}: {}", i + 1, too_large())),
+        }
+    }
+    let result = solved
+        .iter()
+        .try_fold(N::zero(), |sum, (test_val, _, _)| sum.checked_add(test_val))
+        .ok_or_else(|| format!("The result: {}", too_large()))?;
+
+    let elapsed = start.elapsed();
+
+    if options.show_expressions {
+        for (test_val, numbers, operators) in &solved {
+            println!("{}", solver.expression(test_val, numbers, operators));
+        }
+    }
+    if options.show_all {
+        let mut count = 0;
+        for (test_val, numbers, _) in &solved {
+            let solutions = solver.solutions(test_val, numbers).map_err(|_| {
+                format!(
+                    "{}: {}",
+                    solver.expression(test_val, numbers, &[]),
+                    too_large()
+                )
+            })?;
+            for operators in solutions {
+                println!("{}", solver.expression(test_val, numbers, &operators));
+                count += 1;
+            }
+        }
+        println!("Solutions: {}", count);
+    }
+
+    println!("Result: {}", result);
+    println!("(took: {:?})", elapsed);
+    Ok(())
+}
+
+fn main() {
+    let mut options = Options {
+        operators: DEFAULT_OPERATORS.to_string(),
+        show_expressions: false,
+        show_all: false,
+    };
+    let mut number_type = NumberType::U64;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--expressions" => options.show_expressions = true,
+            "--all" => options.show_all = true,
+            "--operators" => options.operators = args.next().unwrap_or_default(),
+            "--u128" => number_type = NumberType::U128,
+            "--big" => number_type = NumberType::Big,
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let result = match number_type {
+        NumberType::U64 => run::<u64>(&options),
+        NumberType::U128 => run::<u128>(&options),
+        NumberType::Big => run::<BigUint>(&options),
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+#[test]
+fn test_expressions() {
+    let solver = Solver::<u64>::parse(DEFAULT_OPERATORS).unwrap();
+    let (test_val, numbers) = parse_line("156: 15 6").unwrap();
+    let operators = solver.test(&test_val, &numbers).unwrap().unwrap();
+    assert_eq!(operators, vec![2]);
+    assert_eq!(
+        solver.expression(&test_val, &numbers, &operators),
+        "156 = 15 || 6"
+    );
+
+    let (test_val, numbers) = parse_line("7290: 6 8 6 15").unwrap();
+    let operators = solver.test(&test_val, &numbers).unwrap().unwrap();
+    assert_eq!(
+        solver.expression(&test_val, &numbers, &operators),
+        "7290 = 6 * 8 || 6 * 15"
+    );
+    assert_eq!(solver.test(&83, &[17, 5]), Ok(None));
+
+    // 2 + 2 and 2 * 2 are both 4
+    let mut all = solver.solutions(&4, &[2, 2, 1]).unwrap();
+    all.sort();
+    assert_eq!(all, vec![vec![0, 0], vec![1, 0]]);
+    assert_eq!(solver.solutions(&2, &[1, 1, 1, 1]).unwrap().len(), 3);
+}
+
+#[test]
+fn test_other_operators() {
+    let solver = Solver::<u64>::parse("-, /, ^, ||2").unwrap();
+    let (test_val, numbers) = parse_line("7: 20 3 2 1").unwrap();
+    let operators = solver.test(&test_val, &numbers).unwrap().unwrap();
+    assert_eq!(solver.evaluate(&numbers, &operators), Ok(7));
+    assert_eq!(
+        solver.expression(&test_val, &numbers, &operators),
+        "7 = 20 - 3 / 2 - 1"
+    );
+
+    // 5 ||2 3 is 0b101 followed by 0b11
+    assert_eq!(solver.test(&0b10111, &[5, 3]), Ok(Some(vec![3])));
+    assert!(Solver::<u64>::parse("+,?").is_err());
+}
+
+#[test]
+fn test_large_divisor() {
+    let solver = Solver::<u64>::parse("/").unwrap();
+    // 3 is any number from 3 * 10^12 up to 4 * 10^12 - 1 divided by 10^12
+    assert_eq!(
+        solver.test(&3, &[3_500_000_000_000, 1_000_000_000_000]),
+        Ok(Some(vec![0]))
+    );
+    assert_eq!(
+        solver.test(&3, &[4_000_000_000_000, 1_000_000_000_000]),
+        Ok(None)
+    );
+    // 3 * 10^12 divided by 10^6 twice, with a range of 10^12 first numbers that work
+    assert_eq!(
+        solver.test(&3, &[3_999_999_999_999, 1_000_000, 1_000_000]),
+        Ok(Some(vec![0, 0]))
+    );
+
+    // The quotient only has to be undone to a range, which contains the first number
+    let solver = Solver::<BigUint>::parse("/,+").unwrap();
+    let (test_val, numbers) =
+        parse_line::<BigUint>("5: 30000000000000000000000012345 10000000000000000000000000000 2")
+            .unwrap();
+    assert_eq!(solver.test(&test_val, &numbers), Ok(Some(vec![0, 1])));
+}
+
+#[test]
+fn test_multiply_by_zero() {
+    let solver = Solver::<u64>::parse(DEFAULT_OPERATORS).unwrap();
+    assert_eq!(solver.test(&0, &[5, 0]), Ok(Some(vec![0])));
+    assert_eq!(solver.test(&1, &[5, 0]), Ok(None));
+    // Any operators work before the 0, as long as the numbers fit
+    assert_eq!(solver.solutions(&0, &[2, 3, 0]).unwrap().len(), 3);
+    assert_eq!(solver.test(&0, &[u64::MAX, 2, 0]), Err(Overflow));
+    let solver = Solver::<u128>::parse(DEFAULT_OPERATORS).unwrap();
+    assert_eq!(
+        solver.test(&0, &[u64::MAX as u128, 2, 0]),
+        Ok(Some(vec![0, 0]))
+    );
+}
+
+#[test]
+fn test_overflow() {
+    let line = "36893488147419103230: 18446744073709551615 2";
+    assert!(parse_line::<u64>(line).is_err());
+    let (test_val, numbers) = parse_line::<u128>(line).unwrap();
+    let solver = Solver::<u128>::parse(DEFAULT_OPERATORS).unwrap();
+    assert_eq!(solver.test(&test_val, &numbers), Ok(Some(vec![0])));
+
+    // 2^64 - 1 would have to be the result of a subtraction
+    let solver = Solver::<u64>::parse("-").unwrap();
+    assert_eq!(solver.test(&u64::MAX, &[5, 1]), Err(Overflow));
+    assert_eq!(solver.test(&3, &[5, 2]), Ok(Some(vec![0])));
+
+    let (test_val, numbers) =
+        parse_line::<BigUint>("1000000000000000000000000000000: 1000000000000000 1000000000000000")
+            .unwrap();
+    let solver = Solver::<BigUint>::parse(DEFAULT_OPERATORS).unwrap();
+    let operators = solver.test(&test_val, &numbers).unwrap().unwrap();
+    assert_eq!(operators, vec![0]);
+    assert_eq!(solver.evaluate(&numbers, &operators), Ok(test_val));
+}
@@ -0,0 +1,55 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::BitXor,
+    str::FromStr,
+};
+
+use num::{BigUint, CheckedAdd, CheckedMul, CheckedSub, FromPrimitive, Integer};
+
+/// The unsigned numbers equations can be solved with
+pub trait Number:
+    Integer
+    + CheckedAdd
+    + CheckedSub
+    + CheckedMul
+    + FromPrimitive
+    + BitXor<Output = Self>
+    + Clone
+    + Debug
+    + Display
+    + FromStr
+{
+    /// How the type is called in messages
+    const NAME: &'static str;
+
+    /// The largest value, if there is one
+    fn max_value() -> Option<Self>;
+}
+
+impl Number for u64 {
+    const NAME: &'static str = "u64";
+
+    fn max_value() -> Option<Self> {
+        Some(u64::MAX)
+    }
+}
+
+impl Number for u128 {
+    const NAME: &'static str = "u128";
+
+    fn max_value() -> Option<Self> {
+        Some(u128::MAX)
+    }
+}
+
+impl Number for BigUint {
+    const NAME: &'static str = "big integers";
+
+    fn max_value() -> Option<Self> {
+        None
+    }
+}
+
+/// A value didn't fit into the number type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
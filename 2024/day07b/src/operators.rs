@@ -0,0 +1,372 @@
+use std::ops::RangeInclusive;
+
+use crate::number::Number;
+
+/// A binary operator that is evaluated left to right
+pub trait Operator<N> {
+    /// How the operator is written in an expression, e.g. `+`
+    fn symbol(&self) -> String;
+
+    /// `left op right`, or why it has no value in `N`
+    fn apply(&self, left: &N, right: &N) -> Result<N, Applied>;
+
+    /// Every `left` for which `left op right` is in `target`. If there's none, the equation
+    /// can't be solved with this operator at this position, which prunes the search. Sets
+    /// `overflow` if some of them don't fit into `N`.
+    fn undo(&self, target: &RangeInclusive<N>, right: &N, overflow: &mut bool) -> Lefts<N>;
+}
+
+/// Why an operator couldn't be applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Applied {
+    /// There's no result, e.g. for a division by zero
+    Undefined,
+    /// The result doesn't fit into the number type
+    Overflow,
+}
+
+/// The values of `left` an operator can be undone to. Undoing a division gives as many as
+/// the divisor, so they are kept as ranges instead of being listed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lefts<N> {
+    None,
+    Range(RangeInclusive<N>),
+    /// Disjoint ranges
+    Ranges(Vec<RangeInclusive<N>>),
+    /// Every value works
+    Any,
+}
+
+impl<N: Number> Lefts<N> {
+    fn range(start: N, end: N) -> Self {
+        if start <= end {
+            Lefts::Range(start..=end)
+        } else {
+            Lefts::None
+        }
+    }
+}
+
+/// The value, or the largest `N` if it doesn't fit
+fn saturate<N: Number>(value: Option<N>, overflow: &mut bool) -> N {
+    value.unwrap_or_else(|| {
+        *overflow = true;
+        N::max_value().expect("only bounded numbers overflow")
+    })
+}
+
+pub struct Add;
+
+impl<N: Number> Operator<N> for Add {
+    fn symbol(&self) -> String {
+        "+".to_string()
+    }
+
+    fn apply(&self, left: &N, right: &N) -> Result<N, Applied> {
+        left.checked_add(right).ok_or(Applied::Overflow)
+    }
+
+    fn undo(&self, target: &RangeInclusive<N>, right: &N, _: &mut bool) -> Lefts<N> {
+        match target.end().checked_sub(right) {
+            Some(end) => Lefts::range(target.start().checked_sub(right).unwrap_or(N::zero()), end),
+            None => Lefts::None,
+        }
+    }
+}
+
+pub struct Multiply;
+
+impl<N: Number> Operator<N> for Multiply {
+    fn symbol(&self) -> String {
+        "*".to_string()
+    }
+
+    fn apply(&self, left: &N, right: &N) -> Result<N, Applied> {
+        left.checked_mul(right).ok_or(Applied::Overflow)
+    }
+
+    /// Multiplying by 0 gives 0 from any `left`
+    fn undo(&self, target: &RangeInclusive<N>, right: &N, _: &mut bool) -> Lefts<N> {
+        if !right.is_zero() {
+            Lefts::range(
+                target.start().div_ceil(right),
+                target.end().clone() / right.clone(),
+            )
+        } else if target.start().is_zero() {
+            Lefts::Any
+        } else {
+            Lefts::None
+        }
+    }
+}
+
+/// Appends the digits of `right` to `left`, in any base
+pub struct Concatenate {
+    pub base: u64,
+}
+
+impl Concatenate {
+    /// The power of the base that shifts `left` past the digits of `right`
+    fn shift<N: Number>(&self, right: &N) -> Option<N> {
+        let base = N::from_u64(self.base)?;
+        let mut shift = base.clone();
+        let mut rest = right.clone() / base.clone();
+        while !rest.is_zero() {
+            shift = shift.checked_mul(&base)?;
+            rest = rest / base.clone();
+        }
+        Some(shift)
+    }
+}
+
+impl<N: Number> Operator<N> for Concatenate {
+    fn symbol(&self) -> String {
+        if self.base == 10 {
+            "||".to_string()
+        } else {
+            format!("||{}", self.base)
+        }
+    }
+
+    fn apply(&self, left: &N, right: &N) -> Result<N, Applied> {
+        self.shift(right)
+            .and_then(|shift| left.checked_mul(&shift))
+            .and_then(|left| left.checked_add(right))
+            .ok_or(Applied::Overflow)
+    }
+
+    fn undo(&self, target: &RangeInclusive<N>, right: &N, _: &mut bool) -> Lefts<N> {
+        // If the shift overflows, `right` alone is already longer than any `target`
+        let Some(shift) = self.shift(right) else {
+            return Lefts::None;
+        };
+        let Some(end) = target.end().checked_sub(right) else {
+            return Lefts::None;
+        };
+        // `right` is smaller than the shift, so `left` only has to be in range
+        let start = target.start().checked_sub(right).unwrap_or(N::zero());
+        Lefts::range(start.div_ceil(&shift), end / shift)
+    }
+}
+
+pub struct Subtract;
+
+impl<N: Number> Operator<N> for Subtract {
+    fn symbol(&self) -> String {
+        "-".to_string()
+    }
+
+    fn apply(&self, left: &N, right: &N) -> Result<N, Applied> {
+        left.checked_sub(right).ok_or(Applied::Undefined)
+    }
+
+    fn undo(&self, target: &RangeInclusive<N>, right: &N, overflow: &mut bool) -> Lefts<N> {
+        let Some(start) = target.start().checked_add(right) else {
+            *overflow = true;
+            return Lefts::None;
+        };
+        Lefts::range(start, saturate(target.end().checked_add(right), overflow))
+    }
+}
+
+/// Integer division, rounding down
+pub struct Divide;
+
+impl<N: Number> Operator<N> for Divide {
+    fn symbol(&self) -> String {
+        "/".to_string()
+    }
+
+    fn apply(&self, left: &N, right: &N) -> Result<N, Applied> {
+        if right.is_zero() {
+            Err(Applied::Undefined)
+        } else {
+            Ok(left.clone() / right.clone())
+        }
+    }
+
+    /// Because of the rounding, there are `right` values of `left` for every value of `target`
+    fn undo(&self, target: &RangeInclusive<N>, right: &N, overflow: &mut bool) -> Lefts<N> {
+        if right.is_zero() {
+            return Lefts::None;
+        }
+        let Some(start) = target.start().checked_mul(right) else {
+            *overflow = true;
+            return Lefts::None;
+        };
+        let end = target
+            .end()
+            .checked_mul(right)
+            .and_then(|end| end.checked_add(&(right.clone() - N::one())));
+        Lefts::range(start, saturate(end, overflow))
+    }
+}
+
+pub struct Xor;
+
+impl<N: Number> Operator<N> for Xor {
+    fn symbol(&self) -> String {
+        "^".to_string()
+    }
+
+    fn apply(&self, left: &N, right: &N) -> Result<N, Applied> {
+        Ok(left.clone() ^ right.clone())
+    }
+
+    /// Xor doesn't keep ranges together, but it moves blocks of a power of two that start at a
+    /// multiple of it as a whole. The target is split into as few of them as possible.
+    fn undo(&self, target: &RangeInclusive<N>, right: &N, _: &mut bool) -> Lefts<N> {
+        let (start, end) = (target.start(), target.end());
+        if start == end {
+            let left = start.clone() ^ right.clone();
+            return Lefts::Range(left.clone()..=left);
+        }
+
+        let two = N::one() + N::one();
+        let mut lefts = vec![];
+        let mut block = start.clone();
+        loop {
+            let mut size = N::one();
+            while let Some(larger) = size.checked_mul(&two) {
+                let fits = block
+                    .checked_add(&(larger.clone() - N::one()))
+                    .is_some_and(|last| last <= *end);
+                if !block.is_multiple_of(&larger) || !fits {
+                    break;
+                }
+                size = larger;
+            }
+            let first = (block.clone() ^ right.clone()) / size.clone() * size.clone();
+            lefts.push(first.clone()..=first + (size.clone() - N::one()));
+            match block.checked_add(&size) {
+                Some(next) if next <= *end => block = next,
+                _ => break,
+            }
+        }
+        Lefts::Ranges(lefts)
+    }
+}
+
+/// Parses an operator symbol, `||` followed by a number is concatenation in that base
+pub fn parse_operator<N: Number>(symbol: &str) -> Option<Box<dyn Operator<N>>> {
+    Some(match symbol {
+        "+" => Box::new(Add),
+        "*" => Box::new(Multiply),
+        "-" => Box::new(Subtract),
+        "/" => Box::new(Divide),
+        "^" => Box::new(Xor),
+        "||" => Box::new(Concatenate { base: 10 }),
+        _ => {
+            let base = symbol.strip_prefix("||")?.parse().ok()?;
+            if base < 2 {
+                return None;
+            }
+            Box::new(Concatenate { base })
+        }
+    })
+}
+
+#[test]
+fn test_operators() {
+    use crate::number::Overflow;
+
+    // Undoes a single value, ranges are small enough to list here
+    let undo = |operator: &dyn Operator<u64>, target, right| {
+        let mut overflow = false;
+        let lefts: Vec<u64> = match operator.undo(&(target..=target), &right, &mut overflow) {
+            Lefts::None => vec![],
+            Lefts::Range(range) => range.collect(),
+            Lefts::Ranges(ranges) => ranges.into_iter().flatten().collect(),
+            Lefts::Any => panic!("{} can't be undone from every left", target),
+        };
+        if overflow {
+            Err(Overflow)
+        } else {
+            Ok(lefts)
+        }
+    };
+
+    for symbol in ["+", "*", "-", "/", "^", "||", "||2", "||16"] {
+        let operator = parse_operator::<u64>(symbol).unwrap();
+        assert_eq!(operator.symbol(), symbol);
+        for (left, right) in [(0, 1), (1, 1), (12, 5), (345, 6), (6, 345), (1000, 10)] {
+            let Ok(target) = operator.apply(&left, &right) else {
+                continue;
+            };
+            assert!(
+                undo(operator.as_ref(), target, right)
+                    .unwrap()
+                    .contains(&left),
+                "{} {} {} = {}",
+                left,
+                symbol,
+                right,
+                target
+            );
+        }
+    }
+
+    let concatenate = Concatenate { base: 10 };
+    assert_eq!(concatenate.apply(&15_u64, &6), Ok(156));
+    assert_eq!(
+        Concatenate { base: 2 }.apply(&0b101_u64, &0b11),
+        Ok(0b10111)
+    );
+    assert_eq!(undo(&concatenate, 156, 7), Ok(vec![]));
+    assert_eq!(undo(&Divide, 3, 4), Ok(vec![12, 13, 14, 15]));
+    assert_eq!(
+        Operator::<u64>::apply(&Subtract, &1, &2),
+        Err(Applied::Undefined)
+    );
+    assert_eq!(
+        Operator::<u64>::apply(&Divide, &1, &0),
+        Err(Applied::Undefined)
+    );
+    assert!(parse_operator::<u64>("||1").is_none());
+    assert!(parse_operator::<u64>("%").is_none());
+
+    // Overflows are reported, not wrapped
+    assert_eq!(undo(&Subtract, u64::MAX, 1), Err(Overflow));
+    assert_eq!(undo(&Divide, u64::MAX / 2, 3), Err(Overflow));
+    assert_eq!(concatenate.apply(&u64::MAX, &1), Err(Applied::Overflow));
+    assert_eq!(Multiply.apply(&u64::MAX, &2), Err(Applied::Overflow));
+    assert_eq!(
+        Operator::<u128>::apply(&concatenate, &(u64::MAX as u128), &1),
+        Ok(u64::MAX as u128 * 10 + 1)
+    );
+
+    // 0 * 0 is 0, whatever `left` is
+    assert_eq!(undo(&Multiply, 5, 0), Ok(vec![]));
+    let mut overflow = false;
+    assert_eq!(Multiply.undo(&(0..=0_u64), &0, &mut overflow), Lefts::Any);
+}
+
+#[test]
+fn test_undo_ranges() {
+    let mut overflow = false;
+    let mut undo =
+        |operator: &dyn Operator<u64>, target, right| operator.undo(&target, &right, &mut overflow);
+    let large = 1_000_000_000_000;
+    assert_eq!(
+        undo(&Divide, 3..=3, large),
+        Lefts::Range(3 * large..=4 * large - 1)
+    );
+    assert_eq!(undo(&Add, 10..=20, 15), Lefts::Range(0..=5));
+    assert_eq!(undo(&Multiply, 10..=20, 3), Lefts::Range(4..=6));
+    assert_eq!(undo(&Multiply, 10..=11, 3), Lefts::None);
+    assert_eq!(undo(&Subtract, 10..=20, 5), Lefts::Range(15..=25));
+    assert_eq!(
+        undo(&Concatenate { base: 10 }, 120..=345, 5),
+        Lefts::Range(12..=34)
+    );
+    assert_eq!(undo(&Xor, 4..=7, 1), Lefts::Ranges(vec![4..=7]));
+    assert_eq!(undo(&Xor, 5..=6, 3), Lefts::Ranges(vec![6..=6, 5..=5]));
+    assert_eq!(
+        undo(&Xor, 3..=9, 2),
+        Lefts::Ranges(vec![1..=1, 4..=7, 10..=11])
+    );
+
+    // The end is cut off where it doesn't fit anymore
+    assert_eq!(undo(&Divide, 1..=u64::MAX, 2), Lefts::Range(2..=u64::MAX));
+    assert!(overflow);
+}
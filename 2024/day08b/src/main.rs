@@ -0,0 +1,445 @@
+use std::{
+    ops::{Add, Div, Mul, RangeInclusive, Sub},
+    time::Instant,
+};
+
+use num::integer::gcd;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+const INPUT: &'static str = include_str!("input.txt");
+
+const USAGE: &str = "Usage: day08b [--harmonics <all|k|a..b|a..=b>] [--coordinates <file>]
+
+Counts the positions with an antinode, in total and per frequency. --harmonics chooses where
+two antennas create antinodes: all (the default) at every grid position in line with them,
+k where one antenna is k times as far away as the other (2 for the first part), or at every
+such ratio in a range.
+
+--coordinates reads the antennas from a file instead of the grid, one per line as the
+frequency followed by the coordinates, e.g. `a 1,2,3`. Antennas can have 2 or 3 dimensions.
+An optional `size 12,12,12` line sets the bounds, otherwise it's the box around the antennas.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Point<const D: usize>([i32; D]);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Vector<const D: usize>([i32; D]);
+
+impl<const D: usize> Sub for Point<D> {
+    type Output = Vector<D>;
+
+    fn sub(self, other: Point<D>) -> Vector<D> {
+        Vector(std::array::from_fn(|i| self.0[i] - other.0[i]))
+    }
+}
+
+impl<const D: usize> Add<Vector<D>> for Point<D> {
+    type Output = Point<D>;
+
+    fn add(self, other: Vector<D>) -> Point<D> {
+        Point(std::array::from_fn(|i| self.0[i] + other.0[i]))
+    }
+}
+
+impl<const D: usize> Sub<Vector<D>> for Point<D> {
+    type Output = Point<D>;
+
+    fn sub(self, other: Vector<D>) -> Point<D> {
+        Point(std::array::from_fn(|i| self.0[i] - other.0[i]))
+    }
+}
+
+impl<const D: usize> Mul<i32> for Vector<D> {
+    type Output = Vector<D>;
+
+    fn mul(self, scalar: i32) -> Vector<D> {
+        Vector(self.0.map(|c| c * scalar))
+    }
+}
+
+impl<const D: usize> Div<i32> for Vector<D> {
+    type Output = Vector<D>;
+
+    fn div(self, scalar: i32) -> Vector<D> {
+        Vector(self.0.map(|c| c / scalar))
+    }
+}
+
+impl<const D: usize> Vector<D> {
+    /// How many times the normalized vector fits into this one
+    pub fn steps(&self) -> i32 {
+        self.0.iter().fold(0, |acc, &c| gcd(acc, c))
+    }
+
+    /// The shortest vector on the grid in the same direction, or `None` for the zero vector
+    pub fn normalize(&self) -> Option<Vector<D>> {
+        let steps = self.steps();
+        (steps != 0).then(|| *self / steps)
+    }
+
+    pub fn checked_mul(&self, scalar: i32) -> Option<Vector<D>> {
+        let mut out = [0; D];
+        for (out, c) in out.iter_mut().zip(self.0) {
+            *out = c.checked_mul(scalar)?;
+        }
+        Some(Vector(out))
+    }
+}
+
+impl<const D: usize> Point<D> {
+    pub fn checked_add(&self, vector: Vector<D>) -> Option<Point<D>> {
+        let mut out = [0; D];
+        for (i, out) in out.iter_mut().enumerate() {
+            *out = self.0[i].checked_add(vector.0[i])?;
+        }
+        Some(Point(out))
+    }
+
+    pub fn checked_sub(&self, vector: Vector<D>) -> Option<Point<D>> {
+        let mut out = [0; D];
+        for (i, out) in out.iter_mut().enumerate() {
+            *out = self.0[i].checked_sub(vector.0[i])?;
+        }
+        Some(Point(out))
+    }
+}
+
+/// An axis-aligned box, `min` is inside and `max` just outside
+struct Bounds<const D: usize> {
+    min: Point<D>,
+    max: Point<D>,
+}
+
+impl<const D: usize> Bounds<D> {
+    fn contains(&self, point: Point<D>) -> bool {
+        (0..D).all(|i| point.0[i] >= self.min.0[i] && point.0[i] < self.max.0[i])
+    }
+}
+
+type Frequency = char;
+
+type Antennas<const D: usize> = FxHashMap<Frequency, Vec<Point<D>>>;
+
+/// Where a pair of antennas creates antinodes
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Harmonics {
+    /// At the grid positions in line with the antennas where one of them is `k` times as far
+    /// away as the other, for every `k` in the range. There are up to two on either side of
+    /// the antennas, and one in the middle for `k` = 1.
+    Ratios(RangeInclusive<i32>),
+    /// At every grid position in line with the antennas, including those between them
+    Line,
+}
+
+impl Harmonics {
+    fn parse(s: &str) -> Option<Harmonics> {
+        if s == "all" {
+            return Some(Harmonics::Line);
+        }
+        let range = if let Some((start, end)) = s.split_once("..=") {
+            start.parse().ok()?..=end.parse().ok()?
+        } else if let Some((start, end)) = s.split_once("..") {
+            start.parse().ok()?..=end.parse::<i32>().ok()?.checked_sub(1)?
+        } else {
+            let k = s.parse().ok()?;
+            k..=k
+        };
+        (*range.start() >= 1).then_some(Harmonics::Ratios(range))
+    }
+}
+
+fn parse_grid(input: &str) -> (Bounds<2>, Antennas<2>) {
+    let lines = input.lines().collect::<Vec<&str>>();
+
+    let width = lines.first().map_or(0, |line| line.len());
+    let height = lines.len();
+    let bounds = Bounds {
+        min: Point([0, 0]),
+        max: Point([width as i32, height as i32]),
+    };
+
+    let mut antennas: Antennas<2> = FxHashMap::default();
+    for (y, line) in lines.iter().enumerate() {
+        for (x, freq) in line.chars().enumerate() {
+            if freq == '.' {
+                continue;
+            }
+            antennas
+                .entry(freq)
+                .or_default()
+                .push(Point([x as i32, y as i32]));
+        }
+    }
+    (bounds, antennas)
+}
+
+/// The number of coordinates in the first antenna or `size` line of a coordinate list
+fn dimensions(input: &str) -> Option<usize> {
+    let line = input.lines().map(str::trim).find(|line| !line.is_empty())?;
+    let (_, coords) = line.split_once(char::is_whitespace)?;
+    Some(coords.split(',').count())
+}
+
+fn parse_coordinates<const D: usize>(input: &str) -> Result<(Bounds<D>, Antennas<D>), String> {
+    let parse_point = |coords: &str| -> Option<Point<D>> {
+        let coords = coords
+            .split(',')
+            .map(|c| c.trim().parse().ok())
+            .collect::<Option<Vec<i32>>>()?;
+        Some(Point(coords.try_into().ok()?))
+    };
+
+    let mut size = None;
+    let mut antennas: Antennas<D> = FxHashMap::default();
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let error = || format!("Line {}: expected {} coordinates, got {:?}", i + 1, D, line);
+        let (name, coords) = line.split_once(char::is_whitespace).ok_or_else(error)?;
+        let point = parse_point(coords).ok_or_else(error)?;
+        let mut chars = name.chars();
+        match (chars.next(), chars.next()) {
+            _ if name == "size" => size = Some(point),
+            (Some(freq), None) => antennas.entry(freq).or_default().push(point),
+            _ => return Err(format!("Line {}: {:?} is not a frequency", i + 1, name)),
+        }
+    }
+
+    let bounds = match size {
+        Some(size) => Bounds {
+            min: Point([0; D]),
+            max: size,
+        },
+        None => {
+            let points = antennas.values().flatten();
+            Bounds {
+                min: Point(std::array::from_fn(|i| {
+                    points.clone().map(|p| p.0[i]).min().unwrap_or(0)
+                })),
+                max: Point(std::array::from_fn(|i| {
+                    points.clone().map(|p| p.0[i] + 1).max().unwrap_or(0)
+                })),
+            }
+        }
+    };
+    Ok((bounds, antennas))
+}
+
+/// The antinodes created by all pairs of antennas of one frequency
+fn antinodes<const D: usize>(
+    points: &[Point<D>],
+    bounds: &Bounds<D>,
+    harmonics: &Harmonics,
+) -> FxHashSet<Point<D>> {
+    let mut antinodes: FxHashSet<Point<D>> = FxHashSet::default();
+    for i in 1..points.len() {
+        for j in 0..i {
+            let p1 = points[i];
+            let p2 = points[j];
+            let offset = p2 - p1;
+            // Two antennas in the same place don't define a line
+            let Some(delta) = offset.normalize() else {
+                continue;
+            };
+            match harmonics {
+                Harmonics::Ratios(range) => {
+                    // For larger k, `offset / (k - 1)` and `offset / (k + 1)` aren't on the
+                    // grid, and neither are the antinodes
+                    let max_k = offset.steps().saturating_add(1);
+                    for k in *range.start()..=*range.end().min(&max_k) {
+                        // `p1 + offset * t` is k times as far away from `p1` as from `p2` for
+                        // t = k / (k - 1) and k / (k + 1), and the other way around for 1 - t
+                        for (num, den) in [(k, k - 1), (-1, k - 1), (k, k + 1), (1, k + 1)] {
+                            if den == 0 {
+                                continue;
+                            }
+                            // Antinodes that far away are outside of any bounds
+                            let Some(scaled) = offset.checked_mul(num) else {
+                                continue;
+                            };
+                            if scaled.0.iter().any(|c| c % den != 0) {
+                                continue;
+                            }
+                            match p1.checked_add(scaled / den) {
+                                Some(antinode) if bounds.contains(antinode) => {
+                                    antinodes.insert(antinode);
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                Harmonics::Line => {
+                    let mut cur = Some(p1);
+                    while let Some(point) = cur.filter(|&p| bounds.contains(p)) {
+                        antinodes.insert(point);
+                        cur = point.checked_add(delta);
+                    }
+
+                    let mut cur = p1.checked_sub(delta);
+                    while let Some(point) = cur.filter(|&p| bounds.contains(p)) {
+                        antinodes.insert(point);
+                        cur = point.checked_sub(delta);
+                    }
+                }
+            }
+        }
+    }
+    antinodes
+}
+
+/// The number of antinodes per frequency, sorted by frequency, and of distinct antinodes
+fn count_antinodes<const D: usize>(
+    bounds: &Bounds<D>,
+    antennas: &Antennas<D>,
+    harmonics: &Harmonics,
+) -> (Vec<(Frequency, usize)>, usize) {
+    let mut all: FxHashSet<Point<D>> = FxHashSet::default();
+    let mut per_frequency = vec![];
+    for (&freq, points) in antennas {
+        let antinodes = antinodes(points, bounds, harmonics);
+        per_frequency.push((freq, antinodes.len()));
+        all.extend(antinodes);
+    }
+    per_frequency.sort();
+    (per_frequency, all.len())
+}
+
+fn run<const D: usize>(bounds: &Bounds<D>, antennas: &Antennas<D>, harmonics: &Harmonics) {
+    let start = Instant::now();
+
+    let (per_frequency, result) = count_antinodes(bounds, antennas, harmonics);
+
+    let elapsed = start.elapsed();
+
+    println!("# Antinodes per frequency");
+    for (freq, count) in per_frequency {
+        println!("  {} {}", freq, count);
+    }
+    println!("Result: {}", result);
+    println!("(took: {:?})", elapsed);
+}
+
+fn main() {
+    let mut harmonics = Harmonics::Line;
+    let mut coordinates = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next()) {
+            ("--harmonics", Some(value)) => match Harmonics::parse(&value) {
+                Some(parsed) => harmonics = parsed,
+                None => {
+                    eprintln!("{}", USAGE);
+                    std::process::exit(1);
+                }
+            },
+            ("--coordinates", Some(path)) => coordinates = Some(path),
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let Some(path) = coordinates else {
+        let (bounds, antennas) = parse_grid(INPUT);
+        run(&bounds, &antennas, &harmonics);
+        return;
+    };
+
+    let input = match std::fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Could not read {}: {}", path, e);
+            std::process::exit(1);
+        }
+    };
+    let result = match dimensions(&input) {
+        Some(2) => parse_coordinates::<2>(&input).map(|(b, a)| run(&b, &a, &harmonics)),
+        Some(3) => parse_coordinates::<3>(&input).map(|(b, a)| run(&b, &a, &harmonics)),
+        _ => Err("Expected antennas with 2 or 3 coordinates".to_string()),
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+
+#[test]
+fn test_harmonics() {
+    let (bounds, antennas) = parse_grid(EXAMPLE);
+    let count = |harmonics| count_antinodes(&bounds, &antennas, &harmonics);
+    assert_eq!(
+        count(Harmonics::parse("2").unwrap()),
+        (vec![('0', 10), ('A', 5)], 14)
+    );
+    assert_eq!(count(Harmonics::Line).1, 34);
+    assert_eq!(Harmonics::parse("1..4"), Some(Harmonics::Ratios(1..=3)));
+    assert_eq!(
+        count(Harmonics::parse("1..=3").unwrap()),
+        count(Harmonics::parse("1..4").unwrap())
+    );
+    // None of the pairs have a midpoint or a point 3 times as far from one antenna on the grid
+    assert_eq!(count(Harmonics::parse("1").unwrap()).1, 0);
+    assert_eq!(count(Harmonics::parse("1..=3").unwrap()).1, 14);
+    assert_eq!(Harmonics::parse("0..3"), None);
+    assert_eq!(Harmonics::parse("1..-2147483648"), None);
+    assert_eq!(Harmonics::parse("x"), None);
+
+    // Between the antennas, one third of the way is half as far from one as from the other
+    let (bounds, antennas) = parse_grid("a..a...");
+    let (_, total) = count_antinodes(&bounds, &antennas, &Harmonics::Ratios(2..=2));
+    assert_eq!(total, 3);
+}
+
+#[test]
+fn test_coordinates() {
+    let input = "size 5,5,5\na 0,0,0\na 2,2,2\nb 1,0,4\nb 1,0,4";
+    assert_eq!(dimensions(input), Some(3));
+    let (bounds, antennas) = parse_coordinates::<3>(input).unwrap();
+
+    // The antinode at 4,4,4 for k = 2, the other one at -2,-2,-2 is outside and those between
+    // the antennas aren't on the grid
+    let (per_frequency, total) = count_antinodes(&bounds, &antennas, &Harmonics::Ratios(2..=2));
+    assert_eq!((per_frequency, total), (vec![('a', 1), ('b', 0)], 1));
+
+    // The whole diagonal, while the two b antennas in one place define no line
+    let (per_frequency, _) = count_antinodes(&bounds, &antennas, &Harmonics::Line);
+    assert_eq!(per_frequency, vec![('a', 5), ('b', 0)]);
+
+    assert!(parse_coordinates::<3>("a 1,2").is_err());
+    assert!(parse_coordinates::<2>("ab 1,2").is_err());
+    let (bounds, _) = parse_coordinates::<2>("a 1,2\nb 3,-1").unwrap();
+    assert!(bounds.contains(Point([3, 2])) && !bounds.contains(Point([0, 2])));
+}
+
+#[test]
+fn test_large_ratios() {
+    let (bounds, antennas) = parse_grid(EXAMPLE);
+    let count = |harmonics| count_antinodes(&bounds, &antennas, &harmonics);
+    assert_eq!(
+        count(Harmonics::parse("1..=2147483647").unwrap()),
+        count(Harmonics::parse("1..=20").unwrap())
+    );
+
+    // Twice the distance doesn't fit into an i32, but the midpoint does
+    let (bounds, antennas) = parse_coordinates::<2>("a 0,0\na 2000000000,0").unwrap();
+    let (_, total) = count_antinodes(&bounds, &antennas, &Harmonics::Ratios(1..=2));
+    assert_eq!(total, 1);
+}
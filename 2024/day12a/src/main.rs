@@ -1,5 +1,7 @@
 use std::time::Instant;
 
+use aoc_utils::grid::BitGrid;
+
 const INPUT: &'static str = include_str!("input.txt");
 
 #[derive(Debug)]
@@ -21,13 +23,13 @@ fn main() {
     let width = plots[0].len();
     let height = plots.len();
 
-    let mut visited: Vec<Vec<bool>> = vec![vec![false; width]; height];
+    let mut visited = BitGrid::new(width, height);
 
     let mut regions: Vec<Region> = Vec::new();
 
     for y in 0..height {
         for x in 0..width {
-            if visited[y][x] {
+            if visited.get(x, y) {
                 continue;
             }
             let c = plots[y][x];
@@ -56,41 +58,41 @@ fn main() {
 fn trace_region(
     region: &mut Region,
     plots: &Vec<Vec<char>>,
-    visited: &mut Vec<Vec<bool>>,
+    visited: &mut BitGrid,
     plant: char,
     x: usize,
     y: usize,
     width: usize,
     height: usize,
 ) {
-    visited[y][x] = true;
+    visited.set(x, y, true);
 
     region.area += 1;
     // Left perimeter
     if x == 0 || plots[y][x - 1] != plant {
         region.perimeter += 1;
-    } else if !visited[y][x - 1] {
+    } else if !visited.get(x - 1, y) {
         trace_region(region, plots, visited, plant, x - 1, y, width, height);
     }
 
     // Right perimeter
     if x == width - 1 || plots[y][x + 1] != plant {
         region.perimeter += 1;
-    } else if !visited[y][x + 1] {
+    } else if !visited.get(x + 1, y) {
         trace_region(region, plots, visited, plant, x + 1, y, width, height);
     }
 
     // Top perimeter
     if y == 0 || plots[y - 1][x] != plant {
         region.perimeter += 1;
-    } else if !visited[y - 1][x] {
+    } else if !visited.get(x, y - 1) {
         trace_region(region, plots, visited, plant, x, y - 1, width, height);
     }
 
     // Bottom perimeter
     if y == height - 1 || plots[y + 1][x] != plant {
         region.perimeter += 1;
-    } else if !visited[y + 1][x] {
+    } else if !visited.get(x, y + 1) {
         trace_region(region, plots, visited, plant, x, y + 1, width, height);
     }
 }
@@ -2,6 +2,8 @@
 
 use std::time::Instant;
 
+use aoc_utils::grid::BitGrid;
+
 const INPUT: &'static str = include_str!("input.txt");
 
 #[derive(Debug)]
@@ -24,13 +26,13 @@ fn main() {
     let width = plots[0].len();
     let height = plots.len();
 
-    let mut visited: Vec<Vec<bool>> = vec![vec![false; width]; height];
+    let mut visited = BitGrid::new(width, height);
 
     let mut regions: Vec<Region> = Vec::new();
 
     for y in 0..height {
         for x in 0..width {
-            if visited[y][x] {
+            if visited.get(x, y) {
                 continue;
             }
             let c = plots[y][x];
@@ -118,14 +120,14 @@ fn is_corner(
 fn trace_region(
     region: &mut Region,
     plots: &Vec<Vec<char>>,
-    visited: &mut Vec<Vec<bool>>,
+    visited: &mut BitGrid,
     plant: char,
     x: usize,
     y: usize,
     width: usize,
     height: usize,
 ) {
-    visited[y][x] = true;
+    visited.set(x, y, true);
 
     region.area += 1;
 
@@ -149,22 +151,22 @@ fn trace_region(
     }
 
     // Follow region left
-    if x > 0 && !visited[y][x - 1] && plots[y][x - 1] == plant {
+    if x > 0 && !visited.get(x - 1, y) && plots[y][x - 1] == plant {
         trace_region(region, plots, visited, plant, x - 1, y, width, height);
     }
 
     // Follow region right
-    if x < width - 1 && !visited[y][x + 1] && plots[y][x + 1] == plant {
+    if x < width - 1 && !visited.get(x + 1, y) && plots[y][x + 1] == plant {
         trace_region(region, plots, visited, plant, x + 1, y, width, height);
     }
 
     // Follow region up
-    if y > 0 && !visited[y - 1][x] && plots[y - 1][x] == plant {
+    if y > 0 && !visited.get(x, y - 1) && plots[y - 1][x] == plant {
         trace_region(region, plots, visited, plant, x, y - 1, width, height);
     }
 
     // Follow region down
-    if y < height - 1 && !visited[y + 1][x] && plots[y + 1][x] == plant {
+    if y < height - 1 && !visited.get(x, y + 1) && plots[y + 1][x] == plant {
         trace_region(region, plots, visited, plant, x, y + 1, width, height);
     }
 }
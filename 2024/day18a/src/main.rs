@@ -1,12 +1,13 @@
 use std::{collections::BinaryHeap, time::Instant};
 
+use aoc_utils::grid::BitGrid;
+
 const INPUT: &'static str = include_str!("input.txt");
 const SIZE: usize = 71;
 const NUM_BYTES: usize = 1024;
 
 #[derive(Clone, Copy)]
 struct Tile {
-    wall: bool,
     cost: Option<usize>,
 }
 
@@ -16,12 +17,6 @@ struct Point {
     y: usize,
 }
 
-impl From<(usize, usize)> for Point {
-    fn from((x, y): (usize, usize)) -> Self {
-        Point { x, y }
-    }
-}
-
 struct Node {
     pos: Point,
     cost: usize,
@@ -50,6 +45,7 @@ impl Ord for Node {
 
 struct Map {
     tiles: Vec<Vec<Tile>>,
+    walls: BitGrid,
     start_pos: Point,
     end_pos: Point,
 }
@@ -75,16 +71,8 @@ fn main() {
     });
 
     let mut map = Map {
-        tiles: vec![
-            vec![
-                Tile {
-                    wall: false,
-                    cost: None
-                };
-                SIZE
-            ];
-            SIZE
-        ],
+        tiles: vec![vec![Tile { cost: None }; SIZE]; SIZE],
+        walls: BitGrid::new(SIZE, SIZE),
         start_pos: Point { x: 0, y: 0 },
         end_pos: Point {
             x: SIZE - 1,
@@ -92,8 +80,8 @@ fn main() {
         },
     };
 
-    for coord in coordinates.take(NUM_BYTES) {
-        map.get_mut(coord.into()).wall = true;
+    for (x, y) in coordinates.take(NUM_BYTES) {
+        map.walls.set(x, y, true);
     }
 
     let mut todos: BinaryHeap<Node> = BinaryHeap::new();
@@ -162,8 +150,11 @@ fn main() {
 }
 
 fn check_pos(map: &mut Map, next_pos: Point, next_cost: usize) -> Option<Node> {
+    if map.walls.get(next_pos.x, next_pos.y) {
+        return None;
+    }
     let next_tile = map.get_mut(next_pos);
-    if !next_tile.wall && (next_tile.cost.is_none() || next_tile.cost > Some(next_cost)) {
+    if next_tile.cost.is_none() || next_tile.cost > Some(next_cost) {
         next_tile.cost = Some(next_cost);
         Some(Node {
             pos: next_pos,
@@ -1,27 +1,17 @@
 use std::{collections::BinaryHeap, fmt::Debug, time::Instant};
 
+use aoc_utils::grid::BitGrid;
+
 const INPUT: &'static str = include_str!("input.txt");
 const SIZE: usize = 71;
 const NUM_BYTES: usize = 1024;
 
-#[derive(Clone, Copy)]
-struct Tile {
-    wall: bool,
-    cost: Option<usize>,
-}
-
 #[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 struct Point {
     x: usize,
     y: usize,
 }
 
-impl From<(usize, usize)> for Point {
-    fn from((x, y): (usize, usize)) -> Self {
-        Point { x, y }
-    }
-}
-
 struct Node {
     pos: Point,
     cost: usize,
@@ -48,25 +38,18 @@ impl Ord for Node {
     }
 }
 
-#[derive(Clone)]
 struct Map {
-    tiles: Vec<Vec<Tile>>,
+    walls: BitGrid,
     start_pos: Point,
     end_pos: Point,
 }
 
 impl Map {
-    fn get(&self, pos: Point) -> &Tile {
-        let Point { x, y } = pos;
-        self.tiles.get(y).unwrap().get(x).unwrap()
-    }
+    /// Searches a path from start to end. Returns whether the end was reached and all tiles that were reached on the way.
+    fn solve(&self) -> (bool, BitGrid) {
+        let mut reached = BitGrid::new(SIZE, SIZE);
+        reached.set(self.start_pos.x, self.start_pos.y, true);
 
-    fn get_mut(&mut self, pos: Point) -> &mut Tile {
-        let Point { x, y } = pos;
-        self.tiles.get_mut(y).unwrap().get_mut(x).unwrap()
-    }
-
-    fn solve(&mut self) -> bool {
         let mut todos: BinaryHeap<Node> = BinaryHeap::new();
         todos.push(Node {
             pos: self.start_pos,
@@ -76,17 +59,16 @@ impl Map {
         while let Some(todo) = todos.pop() {
             let pos = todo.pos;
             if pos == self.end_pos {
-                return true;
+                return (true, reached);
             }
-            let cur_cost = self.get(pos).cost.unwrap();
-            let next_cost = cur_cost + 1;
+            let next_cost = todo.cost + 1;
             if pos.x < SIZE - 1 {
                 // Right
                 let next_pos = Point {
                     x: pos.x + 1,
                     y: pos.y,
                 };
-                if let Some(node) = check_pos(self, next_pos, next_cost) {
+                if let Some(node) = check_pos(self, &mut reached, next_pos, next_cost) {
                     todos.push(node);
                 }
             }
@@ -96,7 +78,7 @@ impl Map {
                     x: pos.x,
                     y: pos.y + 1,
                 };
-                if let Some(node) = check_pos(self, next_pos, next_cost) {
+                if let Some(node) = check_pos(self, &mut reached, next_pos, next_cost) {
                     todos.push(node);
                 }
             }
@@ -106,7 +88,7 @@ impl Map {
                     x: pos.x - 1,
                     y: pos.y,
                 };
-                if let Some(node) = check_pos(self, next_pos, next_cost) {
+                if let Some(node) = check_pos(self, &mut reached, next_pos, next_cost) {
                     todos.push(node);
                 }
             }
@@ -116,23 +98,29 @@ impl Map {
                     x: pos.x,
                     y: pos.y - 1,
                 };
-                if let Some(node) = check_pos(self, next_pos, next_cost) {
+                if let Some(node) = check_pos(self, &mut reached, next_pos, next_cost) {
                     todos.push(node);
                 }
             }
         }
 
-        return false;
+        (false, reached)
     }
 }
 
-impl Debug for Map {
+/// A map together with the tiles reached while solving it
+struct Solution<'a> {
+    map: &'a Map,
+    reached: BitGrid,
+}
+
+impl Debug for Solution<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in self.tiles.iter() {
-            for tile in row.iter() {
-                if tile.wall {
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                if self.map.walls.get(x, y) {
                     write!(f, "▒")?;
-                } else if tile.cost.is_some() {
+                } else if self.reached.get(x, y) {
                     write!(f, "🯅")?;
                 } else {
                     write!(f, " ")?;
@@ -154,16 +142,7 @@ fn main() {
     });
 
     let mut map = Map {
-        tiles: vec![
-            vec![
-                Tile {
-                    wall: false,
-                    cost: None
-                };
-                SIZE
-            ];
-            SIZE
-        ],
+        walls: BitGrid::new(SIZE, SIZE),
         start_pos: Point { x: 0, y: 0 },
         end_pos: Point {
             x: SIZE - 1,
@@ -171,24 +150,21 @@ fn main() {
         },
     };
 
-    for coord in coordinates.clone().take(NUM_BYTES) {
-        map.get_mut(coord.into()).wall = true;
+    for (x, y) in coordinates.clone().take(NUM_BYTES) {
+        map.walls.set(x, y, true);
     }
-    map.get_mut(map.start_pos).cost = Some(0);
-
-    for coord in coordinates.skip(NUM_BYTES) {
-        map.get_mut(coord.into()).wall = true;
-        let mut cur_map = map.clone();
 
-        if !cur_map.solve() {
-            let (x, y) = coord;
+    for (x, y) in coordinates.skip(NUM_BYTES) {
+        map.walls.set(x, y, true);
+        let (solved, reached) = map.solve();
 
+        if !solved {
             let elapsed = start.elapsed();
             println!("Result: {x},{y}");
             println!("(took: {:?})", elapsed);
             println!("");
 
-            println!("{:?}", cur_map);
+            println!("{:?}", Solution { map: &map, reached });
 
             return;
         }
@@ -198,10 +174,9 @@ fn main() {
     println!("No result found, took {:?}", elapsed);
 }
 
-fn check_pos(map: &mut Map, next_pos: Point, next_cost: usize) -> Option<Node> {
-    let next_tile = map.get_mut(next_pos);
-    if !next_tile.wall && (next_tile.cost.is_none() || next_tile.cost > Some(next_cost)) {
-        next_tile.cost = Some(next_cost);
+fn check_pos(map: &Map, reached: &mut BitGrid, next_pos: Point, next_cost: usize) -> Option<Node> {
+    let Point { x, y } = next_pos;
+    if !map.walls.get(x, y) && reached.insert(x, y) {
         Some(Node {
             pos: next_pos,
             cost: next_cost,
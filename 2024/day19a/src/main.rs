@@ -1,4 +1,5 @@
-use rustc_hash::{FxHashMap, FxHashSet};
+use aoc_utils::memo::Memo;
+use rustc_hash::FxHashSet;
 use std::time::Instant;
 
 const INPUT: &'static str = include_str!("input.txt");
@@ -10,7 +11,7 @@ fn main() {
     let patterns: FxHashSet<_> = patterns.split(", ").collect();
     let words: Vec<_> = words.lines().collect();
 
-    let mut cache: FxHashMap<&str, bool> = FxHashMap::default();
+    let mut cache: Memo<&str, bool> = Memo::new();
 
     let possible: Vec<_> = words
         .iter()
@@ -23,30 +24,22 @@ fn main() {
 
     println!("Result: {}", result);
     println!("(took: {:?})", elapsed);
+    println!("(cache: {})", cache.stats());
 }
 
 fn check_word<'a>(
     word: &'a str,
-    cache: &mut FxHashMap<&'a str, bool>,
+    cache: &mut Memo<&'a str, bool>,
     patterns: &FxHashSet<&'a str>,
 ) -> bool {
-    if let Some(ret) = cache.get(word) {
-        return *ret;
-    }
-
-    if patterns.contains(word) {
-        cache.insert(word, true);
-        return true;
-    }
-
-    for pat in patterns {
-        if let Some(suffix) = word.strip_prefix(pat) {
-            if check_word(suffix, cache, patterns) {
-                return true;
-            }
+    cache.get_or_insert_with(word, |cache| {
+        if patterns.contains(word) {
+            return true;
         }
-    }
 
-    cache.insert(word, false);
-    false
+        patterns.iter().any(|pat| {
+            word.strip_prefix(pat)
+                .is_some_and(|suffix| check_word(suffix, cache, patterns))
+        })
+    })
 }
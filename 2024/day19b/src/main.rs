@@ -1,4 +1,5 @@
-use rustc_hash::{FxHashMap, FxHashSet};
+use aoc_utils::memo::Memo;
+use rustc_hash::FxHashSet;
 use std::time::Instant;
 
 const INPUT: &'static str = include_str!("input.txt");
@@ -10,7 +11,7 @@ fn main() {
     let patterns: FxHashSet<_> = patterns.split(", ").collect();
     let words: Vec<_> = words.lines().collect();
 
-    let mut cache: FxHashMap<&str, usize> = FxHashMap::default();
+    let mut cache: Memo<&str, usize> = Memo::new();
 
     let counts: Vec<_> = words
         .iter()
@@ -27,29 +28,27 @@ fn main() {
 
     println!("Result: {}", result);
     println!("(took: {:?})", elapsed);
+    println!("(cache: {})", cache.stats());
 }
 
 fn check_word<'a>(
     word: &'a str,
-    cache: &mut FxHashMap<&'a str, usize>,
+    cache: &mut Memo<&'a str, usize>,
     patterns: &FxHashSet<&'a str>,
 ) -> usize {
-    if let Some(ret) = cache.get(word) {
-        return *ret;
-    }
+    cache.get_or_insert_with(word, |cache| {
+        let mut ret: usize = 0;
 
-    let mut ret: usize = 0;
-
-    if patterns.contains(word) {
-        ret += 1;
-    }
+        if patterns.contains(word) {
+            ret += 1;
+        }
 
-    for pat in patterns {
-        if let Some(suffix) = word.strip_prefix(pat) {
-            ret += check_word(suffix, cache, patterns);
+        for pat in patterns {
+            if let Some(suffix) = word.strip_prefix(pat) {
+                ret += check_word(suffix, cache, patterns);
+            }
         }
-    }
 
-    cache.insert(word, ret);
-    ret
+        ret
+    })
 }
@@ -1,6 +1,6 @@
 use std::{ops::Sub, time::Instant};
 
-use rustc_hash::FxHashMap;
+use aoc_utils::memo::Memo;
 
 const INPUT: &'static str = include_str!("input.txt");
 
@@ -385,7 +385,7 @@ fn main() {
     const DEPTH: usize = 25;
 
     // let mut result: usize = 0;
-    let mut cache: FxHashMap<CacheKey, usize> = FxHashMap::default();
+    let mut cache: Memo<CacheKey, usize> = Memo::new();
     let mut result: usize = 0;
 
     for code in INPUT.trim().lines() {
@@ -406,6 +406,7 @@ fn main() {
 
     println!("Result: {}", result);
     println!("(took: {:?})", elapsed);
+    println!("(cache: {})", cache.stats());
 }
 
 #[derive(Clone, Copy, Hash, Eq, PartialEq)]
@@ -415,7 +416,7 @@ struct CacheKey {
     depth: usize,
 }
 
-fn count_keys(cache: &mut FxHashMap<CacheKey, usize>, keys: &[DirpadKey], depth: usize) -> usize {
+fn count_keys(cache: &mut Memo<CacheKey, usize>, keys: &[DirpadKey], depth: usize) -> usize {
     if depth == 0 {
         return keys.len();
     }
@@ -434,22 +435,18 @@ fn count_keys(cache: &mut FxHashMap<CacheKey, usize>, keys: &[DirpadKey], depth:
                 key,
                 depth,
             };
-            if let Some(&count) = cache.get(&cache_key) {
-                return count;
-            }
-
-            // If the computation was not found in cache, generate the moves to get from prev_key to key
-            let moves = Dirpad::get_cheapest_moves(prev_key, key);
-            let ret = count_keys(cache, &Dirpad::keys_from_moves(&moves), depth - 1);
-            cache.insert(cache_key, ret);
-            ret
+            cache.get_or_insert_with(cache_key, |cache| {
+                // If the computation was not found in cache, generate the moves to get from prev_key to key
+                let moves = Dirpad::get_cheapest_moves(prev_key, key);
+                count_keys(cache, &Dirpad::keys_from_moves(&moves), depth - 1)
+            })
         })
         .sum()
 }
 
 #[test]
 fn test_count_keys() {
-    let mut cache = FxHashMap::default();
+    let mut cache = Memo::new();
     use DirpadKey::*;
     assert_eq!(count_keys(&mut cache, &[Up, Right, Down, A,], 0), 4);
     assert_eq!(count_keys(&mut cache, &[Left], 1), 4);
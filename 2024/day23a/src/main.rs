@@ -0,0 +1,29 @@
+use std::time::Instant;
+
+use aoc_utils::graph::Graph;
+
+const INPUT: &'static str = include_str!("input.txt");
+
+fn main() {
+    let start = Instant::now();
+
+    let graph = Graph::parse_edges(INPUT, '-', false).unwrap();
+
+    let len_3_cycles = graph.cliques_of_size(3);
+
+    // let cycle_nodes: Vec<_> = len_3_cycles
+    //     .iter()
+    //     .map(|c| c.iter().map(|i| graph.name(*i)).collect::<Vec<_>>())
+    //     .collect();
+    // println!("{:#?}", cycle_nodes);
+
+    let result = len_3_cycles
+        .iter()
+        .filter(|c| c.iter().any(|i| graph.name(*i).starts_with("t")))
+        .count();
+
+    let elapsed = start.elapsed();
+
+    println!("Result: {}", result);
+    println!("(took: {:?})", elapsed);
+}
@@ -0,0 +1,29 @@
+use std::time::Instant;
+
+use aoc_utils::graph::Graph;
+
+const INPUT: &'static str = include_str!("input.txt");
+
+fn main() {
+    let start = Instant::now();
+
+    let graph = Graph::parse_edges(INPUT, '-', false).unwrap();
+
+    // find longest clique
+    let cliques = graph.maximal_cliques();
+    let mut longest_clique: Vec<_> = cliques
+        .iter()
+        .max_by_key(|c| c.len())
+        .unwrap()
+        .iter()
+        .map(|i| *graph.name(*i))
+        .collect();
+    longest_clique.sort();
+
+    let result = longest_clique.join(",");
+
+    let elapsed = start.elapsed();
+
+    println!("Result: {}", result);
+    println!("(took: {:?})", elapsed);
+}
@@ -0,0 +1,160 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A solution crate in the workspace, e.g. `2024/day01a` or `2024/day16`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Day {
+    pub year: u16,
+    pub name: String,
+    pub dir: PathBuf,
+}
+
+impl Day {
+    /// The cargo package name, which has to be unique across all years
+    pub fn package(&self) -> String {
+        package_name(self.year, &self.name)
+    }
+
+    /// Key of this day in the answer store
+    pub fn key(&self) -> (u16, String) {
+        (self.year, self.name.clone())
+    }
+
+    pub fn input_path(&self) -> PathBuf {
+        self.dir.join("src").join("input.txt")
+    }
+
+    pub fn input(&self) -> Option<String> {
+        fs::read_to_string(self.input_path()).ok()
+    }
+}
+
+impl Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.name)
+    }
+}
+
+pub fn package_name(year: u16, name: &str) -> String {
+    format!("aoc{}-{}", year, name)
+}
+
+/// Parses a directory name like `2024` as an event year
+pub fn parse_year(name: &str) -> Option<u16> {
+    if name.len() != 4 || !name.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    name.parse().ok()
+}
+
+/// Whether a directory name looks like a solution crate: `day` followed by
+/// two digits and an optional part suffix
+pub fn is_day_name(name: &str) -> bool {
+    let Some(rest) = name.strip_prefix("day") else {
+        return false;
+    };
+    let bytes = rest.as_bytes();
+    bytes.len() >= 2
+        && bytes[..2].iter().all(u8::is_ascii_digit)
+        && bytes[2..].iter().all(u8::is_ascii_lowercase)
+}
+
+fn subdirs(dir: &Path) -> Vec<(String, PathBuf)> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_dir())
+                .filter_map(|e| Some((e.file_name().into_string().ok()?, e.path())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Finds all solution crates in the `<year>/` directories of the workspace root, sorted by year and name
+pub fn discover(root: &Path) -> Vec<Day> {
+    let mut days: Vec<Day> = subdirs(root)
+        .into_iter()
+        .filter_map(|(name, path)| Some((parse_year(&name)?, path)))
+        .flat_map(|(year, path)| {
+            subdirs(&path)
+                .into_iter()
+                .filter(|(name, dir)| is_day_name(name) && dir.join("Cargo.toml").is_file())
+                .map(move |(name, dir)| Day { year, name, dir })
+        })
+        .collect();
+    days.sort();
+    days
+}
+
+/// The most recent year with at least one solution
+pub fn latest_year(days: &[Day]) -> Option<u16> {
+    days.iter().map(|d| d.year).max()
+}
+
+/// Selects the days matching a filter. `2024/16` selects day16 of 2024, a filter without a year
+/// uses the default year. `16` and `day16` select day16, `1` selects day01a and day01b.
+pub fn select<'a>(days: &'a [Day], filter: &str, default_year: u16) -> Vec<&'a Day> {
+    let (year, filter) = match filter.split_once('/') {
+        Some((year, filter)) => match parse_year(year) {
+            Some(year) => (year, filter),
+            None => return vec![],
+        },
+        None => (default_year, filter),
+    };
+
+    let filter = filter.strip_prefix("day").unwrap_or(filter);
+    let (num, part) = filter.split_at(
+        filter
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(filter.len()),
+    );
+    let Ok(num) = num.parse::<u32>() else {
+        return vec![];
+    };
+    let prefix = format!("day{:02}", num);
+
+    days.iter()
+        .filter(|d| {
+            d.year == year
+                && d.name.starts_with(&prefix)
+                && d.name[prefix.len()..].starts_with(part)
+        })
+        .collect()
+}
+
+#[test]
+fn test_select() {
+    let days: Vec<_> = [
+        (2024, "day01a"),
+        (2024, "day01b"),
+        (2024, "day16"),
+        (2025, "day01"),
+    ]
+    .iter()
+    .map(|&(year, name)| Day {
+        year,
+        name: name.to_string(),
+        dir: PathBuf::from(name),
+    })
+    .collect();
+
+    let names = |filter| {
+        select(&days, filter, 2024)
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names("1"), vec!["2024/day01a", "2024/day01b"]);
+    assert_eq!(names("01b"), vec!["2024/day01b"]);
+    assert_eq!(names("day16"), vec!["2024/day16"]);
+    assert_eq!(names("2025/1"), vec!["2025/day01"]);
+    assert_eq!(names("2"), Vec::<&str>::new());
+    assert_eq!(latest_year(&days), Some(2025));
+    assert!(is_day_name("day01a"));
+    assert!(!is_day_name("template"));
+    assert_eq!(package_name(2024, "day01a"), "aoc2024-day01a");
+}
@@ -0,0 +1,343 @@
+use std::{fmt::Write, time::Duration};
+
+use crate::{days::Day, store::Run};
+
+// Everything is inlined, so the dashboard works without any external assets
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; background: #0f0f23; color: #cccccc; }
+a { color: #009900; text-decoration: none; }
+a:hover { color: #99ff99; }
+h1, h2 { color: #00cc00; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+th, td { border: 1px solid #333340; padding: 0.25em 0.75em; text-align: left; vertical-align: top; }
+th { color: #ffff66; }
+.answer { font-family: monospace; color: #ffffff; }
+.muted { color: #666666; }
+pre.grid { font-size: 8px; line-height: 8px; letter-spacing: 0; }
+svg rect { fill: #009900; }
+";
+
+// Colors for grid cells. Index 0 is reserved for background cells.
+const PALETTE: [&str; 13] = [
+    "#333340", "#ff6b6b", "#ffd93d", "#6bcb77", "#4d96ff", "#c77dff", "#ff9f45", "#00c2c7",
+    "#f15bb5", "#9bde7e", "#fee440", "#b8c0ff", "#e07a5f",
+];
+
+fn escape(s: &str) -> String {
+    let mut ret = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => ret.push_str("&amp;"),
+            '<' => ret.push_str("&lt;"),
+            '>' => ret.push_str("&gt;"),
+            '"' => ret.push_str("&quot;"),
+            _ => ret.push(c),
+        }
+    }
+    ret
+}
+
+fn format_ns(ns: u64) -> String {
+    format!("{:?}", Duration::from_nanos(ns))
+}
+
+/// Formats a unix timestamp as UTC date and time
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86400) as i64;
+    let secs = timestamp % 86400;
+
+    // Civil date from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60
+    )
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{}</title><style>{}</style></head><body>{}</body></html>",
+        escape(title),
+        STYLE,
+        body
+    )
+}
+
+fn is_background(c: char) -> bool {
+    matches!(c, '.' | ' ')
+}
+
+/// Renders a grid, coloring each cell by the given color index
+fn render_cells(lines: &[Vec<char>], color: impl Fn(usize, usize) -> usize) -> String {
+    let mut ret = String::from("<pre class=\"grid\">");
+    for (y, line) in lines.iter().enumerate() {
+        // Merge runs of equally colored cells into a single span
+        let mut x = 0;
+        while x < line.len() {
+            let c = color(x, y);
+            let mut end = x + 1;
+            while end < line.len() && color(end, y) == c {
+                end += 1;
+            }
+            let text: String = line[x..end].iter().collect();
+            write!(
+                ret,
+                "<span style=\"color:{}\">{}</span>",
+                PALETTE[c % PALETTE.len()],
+                escape(&text)
+            )
+            .unwrap();
+            x = end;
+        }
+        ret.push('\n');
+    }
+    ret.push_str("</pre>");
+    ret
+}
+
+/// Renders a grid, coloring each cell by its character
+fn render_grid(grid: &[String]) -> String {
+    let lines: Vec<Vec<char>> = grid.iter().map(|l| l.chars().collect()).collect();
+    render_cells(&lines, |x, y| {
+        let c = lines[y][x];
+        if is_background(c) {
+            0
+        } else {
+            1 + (c as usize % (PALETTE.len() - 1))
+        }
+    })
+}
+
+/// Renders a grid, coloring each connected region of equal characters differently
+fn render_regions(lines: &[Vec<char>]) -> String {
+    let height = lines.len();
+    let width = lines[0].len();
+    let mut region: Vec<Vec<usize>> = vec![vec![0; width]; height];
+    let mut next_region = 1;
+
+    for y in 0..height {
+        for x in 0..width {
+            if region[y][x] != 0 || is_background(lines[y][x]) {
+                continue;
+            }
+
+            // Flood fill iteratively, maps can be large
+            let c = lines[y][x];
+            let mut todo = vec![(x, y)];
+            region[y][x] = next_region;
+            while let Some((x, y)) = todo.pop() {
+                let neighbors = [
+                    (x.wrapping_sub(1), y),
+                    (x + 1, y),
+                    (x, y.wrapping_sub(1)),
+                    (x, y + 1),
+                ];
+                for (nx, ny) in neighbors {
+                    if nx < width && ny < height && region[ny][nx] == 0 && lines[ny][nx] == c {
+                        region[ny][nx] = next_region;
+                        todo.push((nx, ny));
+                    }
+                }
+            }
+            next_region += 1;
+        }
+    }
+
+    render_cells(lines, |x, y| match region[y][x] {
+        0 => 0,
+        r => 1 + (r % (PALETTE.len() - 1)),
+    })
+}
+
+/// Returns the input as a grid if all lines have the same length
+pub fn input_grid(input: &str) -> Option<Vec<Vec<char>>> {
+    let lines: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+    let width = lines.first()?.len();
+    (lines.len() >= 3 && width >= 3 && lines.iter().all(|l| l.len() == width)).then_some(lines)
+}
+
+fn render_answers(run: &Run) -> String {
+    run.output
+        .answers
+        .iter()
+        .map(|a| {
+            format!(
+                "{}: <span class=\"answer\">{}</span>",
+                escape(&a.label),
+                escape(&a.value)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+/// Renders the total time of each run as a bar chart
+fn render_timing_chart(history: &[Run]) -> String {
+    const WIDTH: usize = 600;
+    const HEIGHT: usize = 100;
+
+    // Only show as many runs as fit into the chart
+    let history = &history[history.len().saturating_sub(WIDTH)..];
+    let max = history
+        .iter()
+        .map(|r| r.output.total_ns())
+        .max()
+        .unwrap_or(0);
+    if max == 0 {
+        return String::new();
+    }
+    let bar_width = (WIDTH / history.len()).clamp(1, 40);
+
+    let mut svg = format!(
+        "<svg width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\">",
+        WIDTH, HEIGHT
+    );
+    for (i, run) in history.iter().enumerate() {
+        let ns = run.output.total_ns();
+        let height = ((ns as f64 / max as f64) * HEIGHT as f64).max(1.0) as usize;
+        write!(
+            svg,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"><title>{}: {}</title></rect>",
+            i * bar_width,
+            HEIGHT - height,
+            bar_width.saturating_sub(1).max(1),
+            height,
+            format_timestamp(run.timestamp),
+            format_ns(ns)
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+pub fn index(days: &[(&Day, Option<&Vec<Run>>)]) -> String {
+    let mut body = String::from("<h1>Advent of Code</h1>");
+    let mut year = None;
+    for (day, history) in days {
+        if year != Some(day.year) {
+            if year.is_some() {
+                body.push_str("</table>");
+            }
+            year = Some(day.year);
+            write!(
+                body,
+                "<h2>{}</h2><table><tr><th>Day</th><th>Answers</th><th>Last run</th><th>Time</th><th>Runs</th></tr>",
+                day.year
+            )
+            .unwrap();
+        }
+
+        let latest = history.and_then(|h| h.last());
+        write!(
+            body,
+            "<tr><td><a href=\"/day/{}\">{}</a></td>",
+            escape(&day.to_string()),
+            escape(&day.name)
+        )
+        .unwrap();
+        match latest {
+            Some(run) => write!(
+                body,
+                "<td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                render_answers(run),
+                format_timestamp(run.timestamp),
+                format_ns(run.output.total_ns()),
+                history.map_or(0, |h| h.len())
+            )
+            .unwrap(),
+            None => body
+                .push_str("<td class=\"muted\">not run yet</td><td></td><td></td><td>0</td></tr>"),
+        }
+    }
+    if year.is_some() {
+        body.push_str("</table>");
+    }
+    body.push_str("<p class=\"muted\">Record new runs with <code>aoc run &lt;day&gt;</code>. JSON is available at <a href=\"/api/days\">/api/days</a>.</p>");
+    page("Advent of Code", &body)
+}
+
+pub fn day(day: &Day, history: &[Run], input: Option<&str>) -> String {
+    let mut body = format!(
+        "<p><a href=\"/\">&larr; All days</a></p><h1>{0}</h1><p class=\"muted\">JSON: <a href=\"/api/days/{0}\">/api/days/{0}</a></p>",
+        escape(&day.to_string())
+    );
+
+    match history.last() {
+        Some(run) => {
+            write!(
+                body,
+                "<h2>Latest answers</h2><p>{}</p><p class=\"muted\">{}</p>",
+                render_answers(run),
+                format_timestamp(run.timestamp)
+            )
+            .unwrap();
+
+            body.push_str("<h2>Timing history</h2>");
+            body.push_str(&render_timing_chart(history));
+            body.push_str(
+                "<table><tr><th>Run</th><th>Timings</th><th>Total</th><th>Answers</th></tr>",
+            );
+            for run in history.iter().rev() {
+                let timings = run
+                    .output
+                    .timings_ns
+                    .iter()
+                    .map(|&ns| format_ns(ns))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(
+                    body,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    format_timestamp(run.timestamp),
+                    timings,
+                    format_ns(run.output.total_ns()),
+                    render_answers(run)
+                )
+                .unwrap();
+            }
+            body.push_str("</table>");
+
+            for (i, grid) in run.output.grids.iter().enumerate() {
+                write!(body, "<h2>Output grid {}</h2>", i + 1).unwrap();
+                body.push_str(&render_grid(grid));
+            }
+        }
+        None => body.push_str("<p class=\"muted\">This day has not been run yet.</p>"),
+    }
+
+    if let Some(lines) = input.and_then(input_grid) {
+        body.push_str("<h2>Input regions</h2>");
+        body.push_str(&render_regions(&lines));
+    }
+
+    page(&day.to_string(), &body)
+}
+
+pub fn not_found() -> String {
+    page(
+        "Not found",
+        "<h1>Not found</h1><p><a href=\"/\">&larr; All days</a></p>",
+    )
+}
+
+#[test]
+fn test_format_timestamp() {
+    assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+    assert_eq!(format_timestamp(1733011200), "2024-12-01 00:00:00");
+    assert_eq!(format_timestamp(1735689599), "2024-12-31 23:59:59");
+}
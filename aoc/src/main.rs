@@ -0,0 +1,197 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode, Stdio},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use days::Day;
+use output::Output;
+use store::{Run, Store};
+
+mod days;
+mod html;
+mod output;
+mod scaffold;
+mod serve;
+mod store;
+
+const DEFAULT_PORT: u16 = 8024;
+
+const USAGE: &str = "\
+Usage:
+  aoc run [--year <y>] [<day>...]   Runs the given days (all if none are given) and records the results
+  aoc new [--year <y>] <day>        Creates a new solution crate from the template
+  aoc serve [--port <n>]            Serves the results dashboard on http://127.0.0.1:<port>/
+
+Days can be given as `16`, `day16`, `01b` or `2024/16`. `1` selects all parts of day 1.
+Without `--year`, days refer to the most recent year in the workspace.";
+
+fn workspace_root() -> PathBuf {
+    // The runner lives in <root>/aoc
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+fn run_day(root: &Path, store: &Store, day: &Day) -> Result<(), String> {
+    if !day.input_path().is_file() {
+        return Err(format!("missing input {}", day.input_path().display()));
+    }
+
+    println!("Running {}...", day);
+    let result = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .args(["run", "--release", "--quiet", "-p", &day.package()])
+        .current_dir(root)
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("failed to start cargo: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    print!("{}", stdout);
+    if !result.status.success() {
+        return Err(format!("exited with {}", result.status));
+    }
+
+    let run = Run {
+        year: day.year,
+        day: day.name.clone(),
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs(),
+        output: Output::parse(&stdout),
+    };
+    store
+        .append(&run)
+        .map_err(|e| format!("failed to record run: {}", e))
+}
+
+/// Splits `--year <y>` off the arguments
+fn parse_year_arg(args: &[String]) -> Result<(Option<u16>, Vec<String>), String> {
+    let mut year = None;
+    let mut rest = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--year" {
+            let value = args.next().ok_or("Missing value for --year")?;
+            year = Some(days::parse_year(value).ok_or(format!("Invalid year: {}", value))?);
+        } else {
+            rest.push(arg.clone());
+        }
+    }
+    Ok((year, rest))
+}
+
+fn cmd_run(root: &Path, args: &[String]) -> ExitCode {
+    let (year, filters) = match parse_year_arg(args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let days = days::discover(root);
+    let selected: Vec<&Day> = if filters.is_empty() {
+        days.iter()
+            .filter(|d| year.is_none_or(|y| d.year == y))
+            .collect()
+    } else {
+        let default_year = year.or(days::latest_year(&days)).unwrap_or_default();
+        filters
+            .iter()
+            .flat_map(|f| days::select(&days, f, default_year))
+            .collect()
+    };
+    if selected.is_empty() {
+        eprintln!("No matching days found");
+        return ExitCode::FAILURE;
+    }
+
+    let store = Store::new(root);
+    let mut failed = false;
+    for day in selected {
+        if let Err(e) = run_day(root, &store, day) {
+            eprintln!("{}: {}", day, e);
+            failed = true;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn cmd_new(root: &Path, args: &[String]) -> ExitCode {
+    let (year, rest) = match parse_year_arg(args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let [name] = rest.as_slice() else {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+    let Some(year) = year.or(days::latest_year(&days::discover(root))) else {
+        eprintln!("No year found in the workspace, pass --year");
+        return ExitCode::FAILURE;
+    };
+
+    match scaffold::create(root, year, name) {
+        Ok(day) => {
+            println!("Created {} in {}", day, day.dir.display());
+            println!("Put your puzzle input into {}", day.input_path().display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn cmd_serve(root: &Path, args: &[String]) -> ExitCode {
+    let port = match args {
+        [] => DEFAULT_PORT,
+        [flag, port] if flag == "--port" => match port.parse() {
+            Ok(port) => port,
+            Err(_) => {
+                eprintln!("Invalid port: {}", port);
+                return ExitCode::FAILURE;
+            }
+        },
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match serve::serve(root, port) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Server error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let root = workspace_root();
+
+    match args.split_first() {
+        Some((cmd, rest)) if cmd == "run" => cmd_run(&root, rest),
+        Some((cmd, rest)) if cmd == "new" => cmd_new(&root, rest),
+        Some((cmd, rest)) if cmd == "serve" => cmd_serve(&root, rest),
+        _ => {
+            eprintln!("{}", USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
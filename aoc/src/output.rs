@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answer {
+    pub label: String,
+    pub value: String,
+}
+
+/// Everything we can extract from the stdout of a solution
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Output {
+    pub answers: Vec<Answer>,
+    /// The `(took: ...)` timings in nanoseconds, in the order they were printed
+    pub timings_ns: Vec<u64>,
+    /// Blocks of equally wide lines, e.g. maps and plots
+    pub grids: Vec<Vec<String>>,
+}
+
+const MIN_GRID_SIZE: usize = 3;
+
+/// Parses the `Debug` representation of a `Duration`, e.g. `1.5ms` or `12.34µs`
+fn parse_duration_ns(s: &str) -> Option<u64> {
+    let split = s.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (value, unit) = s.split_at(split);
+    let value: f64 = value.parse().ok()?;
+    let factor = match unit {
+        "ns" => 1.0,
+        "µs" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+    Some((value * factor).round() as u64)
+}
+
+fn parse_timing(line: &str) -> Option<u64> {
+    let (_, rest) = line.split_once("took")?;
+    let rest = rest.trim_start_matches(':').trim();
+    let rest = rest.trim_end_matches(')');
+    parse_duration_ns(rest)
+}
+
+fn parse_answer(line: &str) -> Option<Answer> {
+    let (label, value) = line.split_once(": ")?;
+    let valid_label = label.starts_with(|c: char| c.is_ascii_alphabetic())
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == ' ' || c == '-');
+    if !valid_label || value.trim().is_empty() {
+        return None;
+    }
+    Some(Answer {
+        label: label.to_string(),
+        value: value.trim().to_string(),
+    })
+}
+
+impl Output {
+    fn flush_block(&mut self, block: &mut Vec<&str>) {
+        if block.len() >= MIN_GRID_SIZE {
+            self.grids
+                .push(block.iter().map(|l| l.to_string()).collect());
+        }
+        block.clear();
+    }
+
+    pub fn parse(stdout: &str) -> Output {
+        let mut output = Output::default();
+        let mut block: Vec<&str> = vec![];
+
+        for line in stdout.lines() {
+            if let Some(ns) = parse_timing(line) {
+                output.timings_ns.push(ns);
+                output.flush_block(&mut block);
+                continue;
+            }
+            if let Some(answer) = parse_answer(line) {
+                output.answers.push(answer);
+                output.flush_block(&mut block);
+                continue;
+            }
+
+            let width = line.chars().count();
+            if width < MIN_GRID_SIZE {
+                output.flush_block(&mut block);
+                continue;
+            }
+            if let Some(first) = block.first() {
+                if first.chars().count() != width {
+                    output.flush_block(&mut block);
+                }
+            }
+            block.push(line);
+        }
+        output.flush_block(&mut block);
+
+        output
+    }
+
+    pub fn total_ns(&self) -> u64 {
+        self.timings_ns.iter().sum()
+    }
+}
+
+#[test]
+fn test_parse_output() {
+    let stdout = "\
+Result part 1: 7036
+(took: 1.5ms)
+Result part 2: 45
+(took: 250µs)
+
+#####
+#O.E#
+#####
+Min sum: 12 at iteration 7";
+
+    let output = Output::parse(stdout);
+    assert_eq!(
+        output.answers,
+        vec![
+            Answer {
+                label: "Result part 1".into(),
+                value: "7036".into()
+            },
+            Answer {
+                label: "Result part 2".into(),
+                value: "45".into()
+            },
+            Answer {
+                label: "Min sum".into(),
+                value: "12 at iteration 7".into()
+            },
+        ]
+    );
+    assert_eq!(output.timings_ns, vec![1_500_000, 250_000]);
+    assert_eq!(output.grids, vec![vec!["#####", "#O.E#", "#####"]]);
+}
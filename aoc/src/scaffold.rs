@@ -0,0 +1,53 @@
+use std::{fs, path::Path};
+
+use crate::days::{self, Day};
+
+/// Turns `5`, `05b` or `day05b` into a crate directory name like `day05b`
+fn normalize_name(name: &str) -> Option<String> {
+    let name = name.strip_prefix("day").unwrap_or(name);
+    let split = name
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(name.len());
+    let (num, part) = name.split_at(split);
+    let num: u32 = num.parse().ok().filter(|n| (1..=25).contains(n))?;
+    let name = format!("day{:02}{}", num, part);
+    days::is_day_name(&name).then_some(name)
+}
+
+/// Creates `<year>/<day>/` from the template shared by all years
+pub fn create(root: &Path, year: u16, name: &str) -> Result<Day, String> {
+    let name = normalize_name(name).ok_or(format!("Invalid day: {}", name))?;
+    let day = Day {
+        year,
+        dir: root.join(year.to_string()).join(&name),
+        name,
+    };
+    if day.dir.exists() {
+        return Err(format!("{} already exists", day.dir.display()));
+    }
+
+    let template = fs::read_to_string(root.join("template").join("main.rs"))
+        .map_err(|e| format!("Failed to read template: {}", e))?;
+    let manifest = format!(
+        "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n",
+        day.package()
+    );
+
+    let src = day.dir.join("src");
+    fs::create_dir_all(&src)
+        .and_then(|_| fs::write(day.dir.join("Cargo.toml"), manifest))
+        .and_then(|_| fs::write(src.join("main.rs"), template))
+        .and_then(|_| fs::write(day.input_path(), ""))
+        .map_err(|e| format!("Failed to create {}: {}", day.dir.display(), e))?;
+
+    Ok(day)
+}
+
+#[test]
+fn test_normalize_name() {
+    assert_eq!(normalize_name("5").as_deref(), Some("day05"));
+    assert_eq!(normalize_name("05b").as_deref(), Some("day05b"));
+    assert_eq!(normalize_name("day16").as_deref(), Some("day16"));
+    assert_eq!(normalize_name("26"), None);
+    assert_eq!(normalize_name("day5-b"), None);
+}
@@ -0,0 +1,204 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    path::Path,
+};
+
+use serde::Serialize;
+
+use crate::{
+    days::{self, Day},
+    html,
+    output::Answer,
+    store::{Run, Store},
+};
+
+#[derive(Serialize)]
+struct DaySummary<'a> {
+    year: u16,
+    day: &'a str,
+    has_input: bool,
+    runs: usize,
+    latest: Option<&'a Run>,
+}
+
+#[derive(Serialize)]
+struct HistoryEntry<'a> {
+    timestamp: u64,
+    total_ns: u64,
+    timings_ns: &'a [u64],
+    answers: &'a [Answer],
+}
+
+#[derive(Serialize)]
+struct DayDetail<'a> {
+    year: u16,
+    day: &'a str,
+    latest: Option<&'a Run>,
+    history: Vec<HistoryEntry<'a>>,
+    input_grid: Option<Vec<String>>,
+}
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn html(body: String) -> Response {
+        Response {
+            status: "200 OK",
+            content_type: "text/html; charset=utf-8",
+            body,
+        }
+    }
+
+    fn json(value: &impl Serialize) -> Response {
+        Response {
+            status: "200 OK",
+            content_type: "application/json",
+            body: serde_json::to_string_pretty(value).unwrap(),
+        }
+    }
+
+    fn not_found(api: bool) -> Response {
+        Response {
+            status: "404 Not Found",
+            content_type: if api {
+                "application/json"
+            } else {
+                "text/html; charset=utf-8"
+            },
+            body: if api {
+                "{\"error\":\"not found\"}".to_string()
+            } else {
+                html::not_found()
+            },
+        }
+    }
+}
+
+fn route(root: &Path, path: &str) -> Response {
+    // Re-read everything on each request, so new runs show up without restarting the server
+    let days = days::discover(root);
+    let mut history = Store::new(root).load();
+    let find_day = |year: &str, name: &str| {
+        let year = days::parse_year(year)?;
+        days.iter().find(|d| d.year == year && d.name == name)
+    };
+
+    let path = path.split('?').next().unwrap_or(path).trim_end_matches('/');
+    match path.split('/').skip(1).collect::<Vec<_>>().as_slice() {
+        [] | [""] => {
+            let rows: Vec<(&Day, Option<&Vec<Run>>)> =
+                days.iter().map(|d| (d, history.get(&d.key()))).collect();
+            Response::html(html::index(&rows))
+        }
+        ["day", year, name] => match find_day(year, name) {
+            Some(day) => {
+                let runs = history.remove(&day.key()).unwrap_or_default();
+                Response::html(html::day(day, &runs, day.input().as_deref()))
+            }
+            None => Response::not_found(false),
+        },
+        ["api", "days"] => {
+            let summaries: Vec<DaySummary> = days
+                .iter()
+                .map(|d| {
+                    let runs = history.get(&d.key());
+                    DaySummary {
+                        year: d.year,
+                        day: &d.name,
+                        has_input: d.input_path().is_file(),
+                        runs: runs.map_or(0, |r| r.len()),
+                        latest: runs.and_then(|r| r.last()),
+                    }
+                })
+                .collect();
+            Response::json(&summaries)
+        }
+        ["api", "days", year, name] => match find_day(year, name) {
+            Some(day) => {
+                let runs = history.remove(&day.key()).unwrap_or_default();
+                let input_grid = day
+                    .input()
+                    .and_then(|input| html::input_grid(&input))
+                    .map(|lines| lines.iter().map(|l| l.iter().collect()).collect());
+                Response::json(&DayDetail {
+                    year: day.year,
+                    day: &day.name,
+                    latest: runs.last(),
+                    history: runs
+                        .iter()
+                        .map(|r| HistoryEntry {
+                            timestamp: r.timestamp,
+                            total_ns: r.output.total_ns(),
+                            timings_ns: &r.output.timings_ns,
+                            answers: &r.output.answers,
+                        })
+                        .collect(),
+                    input_grid,
+                })
+            }
+            None => Response::not_found(true),
+        },
+        [first, ..] => Response::not_found(*first == "api"),
+    }
+}
+
+fn handle(root: &Path, stream: TcpStream) -> io::Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Skip the headers, we don't need any of them
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let path = parts.next().unwrap_or("/");
+
+    let response = match method {
+        "GET" | "HEAD" => route(root, path),
+        _ => Response {
+            status: "405 Method Not Allowed",
+            content_type: "text/plain",
+            body: "Method not allowed".to_string(),
+        },
+    };
+
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    )?;
+    if method != "HEAD" {
+        stream.write_all(response.body.as_bytes())?;
+    }
+    stream.flush()
+}
+
+/// Serves the dashboard on localhost until the process is killed
+pub fn serve(root: &Path, port: u16) -> io::Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    println!("Serving on http://127.0.0.1:{}/", port);
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(e) = handle(root, stream) {
+                    eprintln!("Error handling request: {}", e);
+                }
+            }
+            Err(e) => eprintln!("Connection failed: {}", e),
+        }
+    }
+    Ok(())
+}
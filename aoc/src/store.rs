@@ -0,0 +1,74 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::output::Output;
+
+/// One run of a solution, as recorded by `aoc run`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Run {
+    #[serde(default = "legacy_year")]
+    pub year: u16,
+    pub day: String,
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub output: Output,
+}
+
+/// Runs recorded before multi-year support didn't store a year
+fn legacy_year() -> u16 {
+    2024
+}
+
+/// Key of a day in the history, e.g. `(2024, "day16")`
+pub type DayKey = (u16, String);
+
+/// Append-only history of all runs, stored as JSON lines
+pub struct Store {
+    path: PathBuf,
+}
+
+impl Store {
+    pub fn new(root: &Path) -> Store {
+        Store {
+            path: root.join(".aoc").join("history.jsonl"),
+        }
+    }
+
+    pub fn append(&self, run: &Run) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(run)?)
+    }
+
+    /// Loads all runs, grouped by year and day and sorted oldest first. Lines that cannot be parsed are skipped.
+    pub fn load(&self) -> BTreeMap<DayKey, Vec<Run>> {
+        let mut runs: BTreeMap<DayKey, Vec<Run>> = BTreeMap::new();
+        let Ok(contents) = fs::read_to_string(&self.path) else {
+            return runs;
+        };
+        for run in contents
+            .lines()
+            .filter_map(|l| serde_json::from_str::<Run>(l).ok())
+        {
+            runs.entry((run.year, run.day.clone()))
+                .or_default()
+                .push(run);
+        }
+        for history in runs.values_mut() {
+            history.sort_by_key(|r| r.timestamp);
+        }
+        runs
+    }
+}
@@ -0,0 +1,476 @@
+use std::{
+    collections::{BTreeSet, VecDeque},
+    fmt::Debug,
+    hash::Hash,
+};
+
+use rustc_hash::FxHashMap;
+
+/// A graph whose nodes are interned names, e.g. `&str` or `u32`.
+/// Nodes are referred to by their index, which is assigned in insertion order.
+#[derive(Clone)]
+pub struct Graph<N> {
+    names: Vec<N>,
+    indizes: FxHashMap<N, usize>,
+    /// Outgoing edges of each node. For undirected graphs, edges are stored in both directions.
+    adjacency: Vec<BTreeSet<usize>>,
+    directed: bool,
+}
+
+impl<N: Hash + Eq + Clone> Graph<N> {
+    pub fn undirected() -> Self {
+        Self::new(false)
+    }
+
+    pub fn directed() -> Self {
+        Self::new(true)
+    }
+
+    fn new(directed: bool) -> Self {
+        Graph {
+            names: vec![],
+            indizes: FxHashMap::default(),
+            adjacency: vec![],
+            directed,
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Returns the index of a node, adding it if it doesn't exist yet
+    pub fn add_node(&mut self, name: N) -> usize {
+        if let Some(&index) = self.indizes.get(&name) {
+            return index;
+        }
+        let index = self.names.len();
+        self.names.push(name.clone());
+        self.indizes.insert(name, index);
+        self.adjacency.push(BTreeSet::new());
+        index
+    }
+
+    pub fn add_edge(&mut self, from: N, to: N) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        self.adjacency[from].insert(to);
+        if !self.directed {
+            self.adjacency[to].insert(from);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn index_of(&self, name: &N) -> Option<usize> {
+        self.indizes.get(name).copied()
+    }
+
+    pub fn name(&self, index: usize) -> &N {
+        &self.names[index]
+    }
+
+    pub fn names(&self) -> &[N] {
+        &self.names
+    }
+
+    /// The outgoing neighbors of a node
+    pub fn neighbors(&self, index: usize) -> &BTreeSet<usize> {
+        &self.adjacency[index]
+    }
+
+    pub fn has_edge(&self, from: usize, to: usize) -> bool {
+        self.adjacency[from].contains(&to)
+    }
+
+    /// All edges. For undirected graphs, each edge is only returned once.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.adjacency
+            .iter()
+            .enumerate()
+            .flat_map(move |(from, to)| {
+                to.iter()
+                    .filter(move |&&to| self.directed || from <= to)
+                    .map(move |&to| (from, to))
+            })
+    }
+
+    /// Shortest distance in edges from `start` to every node, if reachable
+    pub fn bfs(&self, start: usize) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.len()];
+        let mut todos = VecDeque::from([start]);
+        distances[start] = Some(0);
+
+        while let Some(current) = todos.pop_front() {
+            let next_distance = distances[current].map(|d| d + 1);
+            for &neighbor in &self.adjacency[current] {
+                if distances[neighbor].is_none() {
+                    distances[neighbor] = next_distance;
+                    todos.push_back(neighbor);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Groups the nodes into connected components. Edge directions are ignored.
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        // Union-find, so directed edges connect in both directions
+        let mut parents: Vec<usize> = (0..self.len()).collect();
+        fn find(parents: &mut [usize], node: usize) -> usize {
+            let mut root = node;
+            while parents[root] != root {
+                root = parents[root];
+            }
+            // Compress the path for later lookups
+            let mut node = node;
+            while parents[node] != root {
+                let next = parents[node];
+                parents[node] = root;
+                node = next;
+            }
+            root
+        }
+
+        for (from, to) in self.edges() {
+            let (a, b) = (find(&mut parents, from), find(&mut parents, to));
+            if a != b {
+                parents[a.max(b)] = a.min(b);
+            }
+        }
+
+        let mut components: FxHashMap<usize, Vec<usize>> = FxHashMap::default();
+        for node in 0..self.len() {
+            let root = find(&mut parents, node);
+            components.entry(root).or_default().push(node);
+        }
+        let mut components: Vec<_> = components.into_values().collect();
+        components.sort();
+        components
+    }
+
+    /// Sorts the nodes of a directed graph so that every edge points forward, using Kahn's algorithm.
+    /// Ties are broken by node index. If the graph has a cycle, one of the cycles is returned instead.
+    /// Panics for undirected graphs.
+    pub fn toposort(&self) -> Result<Vec<usize>, Vec<usize>> {
+        assert!(
+            self.directed,
+            "Only directed graphs can be sorted topologically"
+        );
+        let mut in_degree = vec![0usize; self.len()];
+        for (_, to) in self.edges() {
+            in_degree[to] += 1;
+        }
+
+        let mut ready: BTreeSet<usize> = (0..self.len()).filter(|&n| in_degree[n] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+
+        while let Some(node) = ready.pop_first() {
+            order.push(node);
+            for &next in &self.adjacency[node] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.insert(next);
+                }
+            }
+        }
+
+        if order.len() == self.len() {
+            Ok(order)
+        } else {
+            Err(self.find_cycle(&in_degree))
+        }
+    }
+
+    /// Finds a cycle among the nodes that Kahn's algorithm couldn't sort
+    fn find_cycle(&self, in_degree: &[usize]) -> Vec<usize> {
+        // Every unsorted node has a predecessor that is also unsorted, so walking
+        // backwards from any of them must eventually revisit a node
+        let mut predecessor = vec![None; self.len()];
+        for (from, to) in self.edges() {
+            if in_degree[from] > 0 && in_degree[to] > 0 {
+                predecessor[to] = Some(from);
+            }
+        }
+
+        let start = (0..self.len()).find(|&n| in_degree[n] > 0).unwrap();
+        let mut seen = vec![false; self.len()];
+        let mut node = start;
+        while !seen[node] {
+            seen[node] = true;
+            node = predecessor[node].unwrap();
+        }
+
+        // `node` is on the cycle, collect it in edge direction
+        let mut cycle = vec![node];
+        let mut current = predecessor[node].unwrap();
+        while current != node {
+            cycle.push(current);
+            current = predecessor[current].unwrap();
+        }
+        cycle.reverse();
+        cycle
+    }
+
+    /// For every node, all nodes that can be reached by following one or more edges
+    pub fn transitive_closure(&self) -> Vec<BTreeSet<usize>> {
+        (0..self.len())
+            .map(|start| {
+                let mut reached = BTreeSet::new();
+                let mut todos: Vec<usize> = self.adjacency[start].iter().copied().collect();
+                while let Some(node) = todos.pop() {
+                    if reached.insert(node) {
+                        todos.extend(&self.adjacency[node]);
+                    }
+                }
+                reached
+            })
+            .collect()
+    }
+
+    /// Whether `to` can be reached from `from` without using the edge between them
+    fn has_indirect_path(&self, from: usize, to: usize) -> bool {
+        let mut seen = vec![false; self.len()];
+        seen[from] = true;
+        let mut todos: Vec<usize> = self.adjacency[from]
+            .iter()
+            .copied()
+            .filter(|&n| n != to)
+            .collect();
+        while let Some(node) = todos.pop() {
+            if node == to {
+                return true;
+            }
+            if !seen[node] {
+                seen[node] = true;
+                todos.extend(&self.adjacency[node]);
+            }
+        }
+        false
+    }
+
+    /// Edges of a directed graph that are implied by a longer path between the same nodes.
+    /// For a DAG, removing all of them gives the transitive reduction. With cycles, each edge
+    /// is only redundant as long as the others are kept.
+    pub fn redundant_edges(&self) -> Vec<(usize, usize)> {
+        self.edges()
+            .filter(|&(from, to)| self.has_indirect_path(from, to))
+            .collect()
+    }
+
+    /// All sets of `size` nodes that are pairwise connected, each sorted by index
+    pub fn cliques_of_size(&self, size: usize) -> Vec<Vec<usize>> {
+        fn extend<N>(
+            graph: &Graph<N>,
+            clique: &mut Vec<usize>,
+            candidates: &BTreeSet<usize>,
+            size: usize,
+            result: &mut Vec<Vec<usize>>,
+        ) {
+            if clique.len() == size {
+                result.push(clique.clone());
+                return;
+            }
+            for &node in candidates {
+                // Only extend with larger indizes, so each clique is found once
+                let next: BTreeSet<usize> = candidates
+                    .range(node + 1..)
+                    .filter(|&&other| graph.adjacency[node].contains(&other))
+                    .copied()
+                    .collect();
+                clique.push(node);
+                extend(graph, clique, &next, size, result);
+                clique.pop();
+            }
+        }
+
+        let mut result = vec![];
+        if size > 0 {
+            let all = (0..self.len()).collect();
+            extend(self, &mut vec![], &all, size, &mut result);
+        }
+        result
+    }
+
+    /// All cliques that can't be extended by another node, using Bron-Kerbosch with pivoting
+    pub fn maximal_cliques(&self) -> Vec<Vec<usize>> {
+        fn bron_kerbosch<N>(
+            graph: &Graph<N>,
+            clique: &mut Vec<usize>,
+            mut candidates: BTreeSet<usize>,
+            mut already_found: BTreeSet<usize>,
+            result: &mut Vec<Vec<usize>>,
+        ) {
+            if candidates.is_empty() && already_found.is_empty() {
+                let mut found = clique.clone();
+                found.sort_unstable();
+                result.push(found);
+                return;
+            }
+
+            // Neighbors of the pivot will be found through the pivot, skip them here
+            let pivot = candidates
+                .union(&already_found)
+                .max_by_key(|&&n| graph.adjacency[n].intersection(&candidates).count())
+                .copied()
+                .unwrap();
+            let todo: Vec<usize> = candidates
+                .difference(&graph.adjacency[pivot])
+                .copied()
+                .collect();
+
+            for node in todo {
+                let neighbors = &graph.adjacency[node];
+                clique.push(node);
+                bron_kerbosch(
+                    graph,
+                    clique,
+                    candidates.intersection(neighbors).copied().collect(),
+                    already_found.intersection(neighbors).copied().collect(),
+                    result,
+                );
+                clique.pop();
+                candidates.remove(&node);
+                already_found.insert(node);
+            }
+        }
+
+        let mut result = vec![];
+        bron_kerbosch(
+            self,
+            &mut vec![],
+            (0..self.len()).collect(),
+            BTreeSet::new(),
+            &mut result,
+        );
+        result
+    }
+}
+
+impl<'a> Graph<&'a str> {
+    /// Parses one edge per line, e.g. `a-b` or `a|b`. Empty lines are skipped.
+    pub fn parse_edges(input: &'a str, separator: char, directed: bool) -> Result<Self, String> {
+        let mut graph = Self::new(directed);
+        for (i, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (from, to) = line
+                .split_once(separator)
+                .ok_or_else(|| format!("Line {}: expected an edge, got {:?}", i + 1, line))?;
+            graph.add_edge(from.trim(), to.trim());
+        }
+        Ok(graph)
+    }
+}
+
+impl<N: Debug> Debug for Graph<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut map = f.debug_map();
+        for (name, neighbors) in self.names.iter().zip(self.adjacency.iter()) {
+            let neighbors: Vec<_> = neighbors.iter().map(|&n| &self.names[n]).collect();
+            map.entry(name, &neighbors);
+        }
+        map.finish()
+    }
+}
+
+#[test]
+fn test_parse_edges() {
+    let graph = Graph::parse_edges("a-b\nb-c\n\nd-e", '-', false).unwrap();
+    assert_eq!(graph.len(), 5);
+    assert_eq!(graph.names(), &["a", "b", "c", "d", "e"]);
+    assert!(graph.has_edge(1, 0));
+    assert_eq!(graph.edges().count(), 3);
+    assert_eq!(
+        graph.connected_components(),
+        vec![vec![0, 1, 2], vec![3, 4]]
+    );
+    assert_eq!(graph.bfs(0), vec![Some(0), Some(1), Some(2), None, None]);
+
+    assert!(Graph::parse_edges("a-b\nc", '-', false).is_err());
+}
+
+#[test]
+fn test_toposort() {
+    let graph = Graph::parse_edges("47|53\n97|13\n97|47\n75|53\n47|13", '|', true).unwrap();
+    let order: Vec<_> = graph
+        .toposort()
+        .unwrap()
+        .into_iter()
+        .map(|n| *graph.name(n))
+        .collect();
+    assert_eq!(order, vec!["97", "47", "13", "75", "53"]);
+
+    let cyclic = Graph::parse_edges("a|b\nb|c\nc|a\nc|d", '|', true).unwrap();
+    let cycle: Vec<_> = cyclic
+        .toposort()
+        .unwrap_err()
+        .into_iter()
+        .map(|n| *cyclic.name(n))
+        .collect();
+    assert_eq!(cycle.len(), 3);
+    for (i, from) in cycle.iter().enumerate() {
+        let to = cycle[(i + 1) % cycle.len()];
+        let (from, to) = (
+            cyclic.index_of(from).unwrap(),
+            cyclic.index_of(&to).unwrap(),
+        );
+        assert!(cyclic.has_edge(from, to));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_toposort_undirected() {
+    let graph = Graph::parse_edges("a-b", '-', false).unwrap();
+    let _ = graph.toposort();
+}
+
+#[test]
+fn test_closure() {
+    let graph = Graph::parse_edges("a|b\nb|c\na|c\nc|d", '|', true).unwrap();
+    assert_eq!(
+        graph.transitive_closure(),
+        vec![
+            BTreeSet::from([1, 2, 3]),
+            BTreeSet::from([2, 3]),
+            BTreeSet::from([3]),
+            BTreeSet::new()
+        ]
+    );
+    assert_eq!(graph.redundant_edges(), vec![(0, 2)]);
+
+    // The path back through `a` doesn't make `a|b` redundant
+    let cyclic = Graph::parse_edges("a|b\na|c\nc|a", '|', true).unwrap();
+    assert!(cyclic.redundant_edges().is_empty());
+    assert_eq!(cyclic.transitive_closure()[2], BTreeSet::from([0, 1, 2]));
+}
+
+#[test]
+fn test_cliques() {
+    // Two triangles sharing an edge, plus a square
+    let graph =
+        Graph::parse_edges("a-b\nb-c\nc-a\nb-d\nc-d\nw-x\nx-y\ny-z\nz-w", '-', false).unwrap();
+    let names = |cliques: Vec<Vec<usize>>| {
+        let mut names: Vec<String> = cliques
+            .iter()
+            .map(|c| c.iter().map(|&n| *graph.name(n)).collect())
+            .collect();
+        names.sort();
+        names
+    };
+
+    assert_eq!(names(graph.cliques_of_size(3)), vec!["abc", "bcd"]);
+    assert_eq!(
+        names(graph.maximal_cliques()),
+        vec!["abc", "bcd", "wx", "wz", "xy", "yz"]
+    );
+}
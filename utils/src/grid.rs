@@ -0,0 +1,198 @@
+use std::fmt::Debug;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A fixed-size grid of booleans, packed into 64 bit words.
+/// Cells are addressed by `(x, y)`, rows are stored one after the other.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Creates a grid with all cells unset
+    pub fn new(width: usize, height: usize) -> Self {
+        BitGrid {
+            width,
+            height,
+            words: vec![0; (width * height).div_ceil(WORD_BITS)],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the given coordinates are inside the grid
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    fn bit(&self, x: usize, y: usize) -> (usize, u64) {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside of the {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        let index = y * self.width + x;
+        (index / WORD_BITS, 1 << (index % WORD_BITS))
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let (word, mask) = self.bit(x, y);
+        self.words[word] & mask != 0
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        let (word, mask) = self.bit(x, y);
+        if value {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+    }
+
+    /// Sets a cell and returns whether it was unset before
+    pub fn insert(&mut self, x: usize, y: usize) -> bool {
+        let (word, mask) = self.bit(x, y);
+        let was_unset = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        was_unset
+    }
+
+    /// The number of set cells
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Unsets all cells
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    fn assert_same_size(&self, other: &BitGrid) {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "Grid sizes differ: {}x{} vs. {}x{}",
+            self.width,
+            self.height,
+            other.width,
+            other.height
+        );
+    }
+
+    /// Sets all cells that are set in `other`
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.assert_same_size(other);
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a |= b;
+        }
+    }
+
+    /// Unsets all cells that are not set in `other`
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.assert_same_size(other);
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a &= b;
+        }
+    }
+
+    /// Unsets all cells that are set in `other`
+    pub fn difference_with(&mut self, other: &BitGrid) {
+        self.assert_same_size(other);
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a &= !b;
+        }
+    }
+
+    /// Iterates over the coordinates of all set cells, row by row
+    pub fn iter_ones(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                let index = i * WORD_BITS + bit;
+                Some((index % self.width, index / self.width))
+            })
+        })
+    }
+}
+
+impl Debug for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", if self.get(x, y) { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_get_set() {
+    // Not a multiple of 64, so rows span word boundaries
+    let mut grid = BitGrid::new(13, 11);
+    assert!(grid.is_empty());
+    assert!(grid.insert(12, 10));
+    assert!(!grid.insert(12, 10));
+    grid.set(4, 5, true);
+    assert!(grid.get(4, 5));
+    assert!(!grid.get(5, 4));
+    assert_eq!(grid.count_ones(), 2);
+    assert_eq!(grid.iter_ones().collect::<Vec<_>>(), vec![(4, 5), (12, 10)]);
+
+    grid.set(4, 5, false);
+    assert_eq!(grid.count_ones(), 1);
+    grid.clear();
+    assert!(grid.is_empty());
+
+    assert!(grid.contains(0, 0));
+    assert!(!grid.contains(-1, 0));
+    assert!(!grid.contains(13, 0));
+}
+
+#[test]
+fn test_set_operations() {
+    let mut a = BitGrid::new(3, 3);
+    let mut b = BitGrid::new(3, 3);
+    a.insert(0, 0);
+    a.insert(1, 1);
+    b.insert(1, 1);
+    b.insert(2, 2);
+
+    let mut union = a.clone();
+    union.union_with(&b);
+    assert_eq!(format!("{:?}", union), "#..\n.#.\n..#\n");
+
+    let mut intersection = a.clone();
+    intersection.intersect_with(&b);
+    assert_eq!(intersection.iter_ones().collect::<Vec<_>>(), vec![(1, 1)]);
+
+    a.difference_with(&b);
+    assert_eq!(a.iter_ones().collect::<Vec<_>>(), vec![(0, 0)]);
+}
+
+#[test]
+#[should_panic]
+fn test_out_of_bounds() {
+    BitGrid::new(3, 3).get(3, 0);
+}
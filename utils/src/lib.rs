@@ -0,0 +1,7 @@
+//! Helpers shared by the solutions of all years
+
+pub mod graph;
+pub mod grid;
+pub mod memo;
+pub mod pattern;
+pub mod scan;
@@ -0,0 +1,224 @@
+use std::{collections::BTreeMap, fmt::Display, hash::Hash};
+
+use rustc_hash::FxHashMap;
+
+/// What to do when a bounded cache is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eviction {
+    /// Never evict, the cache grows without bounds
+    None,
+    /// Keep at most this many entries, evicting the oldest insertion first
+    Bounded(usize),
+    /// Keep at most this many entries, evicting the least recently used first
+    Lru(usize),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+impl Stats {
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses, {} evictions, {:.1}% hit rate",
+            self.hits,
+            self.misses,
+            self.evictions,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+struct Entry<V> {
+    value: V,
+    /// Position in the eviction order
+    tick: u64,
+}
+
+/// Cache for the results of a recursive function
+///
+/// ```
+/// use aoc_utils::memo::Memo;
+///
+/// fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+///     memo.get_or_insert_with(n, |memo| match n {
+///         0 | 1 => n,
+///         _ => fib(n - 1, memo) + fib(n - 2, memo),
+///     })
+/// }
+///
+/// let mut memo = Memo::new();
+/// assert_eq!(fib(90, &mut memo), 2880067194370816120);
+/// assert_eq!(memo.stats().misses, 91);
+/// ```
+pub struct Memo<K, V> {
+    entries: FxHashMap<K, Entry<V>>,
+    /// Keys by tick, oldest first. Only maintained for bounded caches.
+    order: BTreeMap<u64, K>,
+    tick: u64,
+    eviction: Eviction,
+    stats: Stats,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+    /// Creates an unbounded cache
+    pub fn new() -> Self {
+        Self::with_eviction(Eviction::None)
+    }
+
+    pub fn with_eviction(eviction: Eviction) -> Self {
+        if let Eviction::Bounded(0) | Eviction::Lru(0) = eviction {
+            panic!("A bounded cache needs a capacity of at least 1");
+        }
+        Memo {
+            entries: FxHashMap::default(),
+            order: BTreeMap::new(),
+            tick: 0,
+            eviction,
+            stats: Stats::default(),
+        }
+    }
+
+    fn capacity(&self) -> Option<usize> {
+        match self.eviction {
+            Eviction::None => None,
+            Eviction::Bounded(capacity) | Eviction::Lru(capacity) => Some(capacity),
+        }
+    }
+
+    /// Looks up a cached value and records a hit or miss
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let Some(entry) = self.entries.get_mut(key) else {
+            self.stats.misses += 1;
+            return None;
+        };
+        self.stats.hits += 1;
+
+        if let Eviction::Lru(_) = self.eviction {
+            // Move the entry to the back of the eviction order
+            self.order.remove(&entry.tick);
+            self.tick += 1;
+            entry.tick = self.tick;
+            self.order.insert(self.tick, key.clone());
+        }
+
+        Some(entry.value.clone())
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.tick += 1;
+        let tick = self.tick;
+
+        if let Some(capacity) = self.capacity() {
+            if let Some(old) = self.entries.get(&key) {
+                self.order.remove(&old.tick);
+            } else if self.entries.len() >= capacity {
+                if let Some((_, oldest)) = self.order.pop_first() {
+                    self.entries.remove(&oldest);
+                    self.stats.evictions += 1;
+                }
+            }
+            self.order.insert(tick, key.clone());
+        }
+
+        self.entries.insert(key, Entry { value, tick });
+    }
+
+    /// Returns the cached value for `key`, or computes and caches it. The cache is passed
+    /// to `compute`, so it can be used for recursive calls.
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = compute(self);
+        self.insert(key, value.clone());
+        value
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Removes all entries, but keeps the statistics
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+#[test]
+fn test_hits_and_misses() {
+    let mut memo: Memo<u32, u32> = Memo::new();
+    assert_eq!(memo.get_or_insert_with(1, |_| 10), 10);
+    assert_eq!(memo.get_or_insert_with(1, |_| unreachable!()), 10);
+    assert_eq!(memo.get(&2), None);
+    assert_eq!(
+        memo.stats(),
+        Stats {
+            hits: 1,
+            misses: 2,
+            evictions: 0
+        }
+    );
+}
+
+#[test]
+fn test_bounded_evicts_oldest_insertion() {
+    let mut memo: Memo<u32, u32> = Memo::with_eviction(Eviction::Bounded(2));
+    memo.insert(1, 1);
+    memo.insert(2, 2);
+    // Reading doesn't protect an entry from eviction
+    assert_eq!(memo.get(&1), Some(1));
+    memo.insert(3, 3);
+    assert_eq!(memo.len(), 2);
+    assert_eq!(memo.get(&1), None);
+    assert_eq!(memo.get(&2), Some(2));
+    assert_eq!(memo.stats().evictions, 1);
+}
+
+#[test]
+fn test_lru_evicts_least_recently_used() {
+    let mut memo: Memo<u32, u32> = Memo::with_eviction(Eviction::Lru(2));
+    memo.insert(1, 1);
+    memo.insert(2, 2);
+    assert_eq!(memo.get(&1), Some(1));
+    memo.insert(3, 3);
+    assert_eq!(memo.get(&2), None);
+    assert_eq!(memo.get(&1), Some(1));
+    assert_eq!(memo.get(&3), Some(3));
+
+    // Overwriting an entry doesn't evict anything
+    memo.insert(3, 4);
+    assert_eq!(memo.len(), 2);
+    assert_eq!(memo.get(&3), Some(4));
+    assert_eq!(memo.stats().evictions, 1);
+}
@@ -0,0 +1,236 @@
+use std::{collections::BTreeSet, fmt::Debug};
+
+const WILDCARD: char = '.';
+
+/// A small 2D pattern of characters, where `.` matches anything
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Pattern {
+    width: usize,
+    height: usize,
+    /// Row by row, `None` is a wildcard
+    cells: Vec<Option<char>>,
+}
+
+impl Pattern {
+    /// Parses a pattern with rows separated by `/` or newlines, e.g. `M.S / .A. / M.S`.
+    /// Whitespace around the rows is ignored.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let rows: Vec<&str> = text
+            .split(['/', '\n'])
+            .map(|row| row.trim())
+            .filter(|row| !row.is_empty())
+            .collect();
+        let width = rows.first().ok_or("Empty pattern")?.chars().count();
+        if let Some(row) = rows.iter().find(|row| row.chars().count() != width) {
+            return Err(format!("Row {:?} doesn't have {} cells", row, width));
+        }
+        Ok(Pattern {
+            width,
+            height: rows.len(),
+            cells: rows
+                .iter()
+                .flat_map(|row| row.chars())
+                .map(|c| (c != WILDCARD).then_some(c))
+                .collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The required character at `(x, y)`, `None` for wildcards
+    pub fn get(&self, x: usize, y: usize) -> Option<char> {
+        self.cells[y * self.width + x]
+    }
+
+    fn from_fn(width: usize, height: usize, f: impl Fn(usize, usize) -> Option<char>) -> Self {
+        Pattern {
+            width,
+            height,
+            cells: (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| f(x, y))
+                .collect(),
+        }
+    }
+
+    /// Rotated by 90 degrees clockwise
+    pub fn rotated(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self.get(y, self.height - 1 - x)
+        })
+    }
+
+    /// Mirrored left to right
+    pub fn reflected(&self) -> Self {
+        Self::from_fn(self.width, self.height, |x, y| {
+            self.get(self.width - 1 - x, y)
+        })
+    }
+
+    /// All distinct rotations and reflections, starting with the pattern itself
+    pub fn variants(&self) -> Vec<Pattern> {
+        let mut variants: Vec<Pattern> = vec![];
+        for start in [self.clone(), self.reflected()] {
+            let mut current = start;
+            for _ in 0..4 {
+                let next = current.rotated();
+                if !variants.contains(&current) {
+                    variants.push(current);
+                }
+                current = next;
+            }
+        }
+        variants
+    }
+}
+
+impl Debug for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", self.get(x, y).unwrap_or(WILDCARD))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PatternMatch {
+    /// Top left corner of the match
+    pub x: usize,
+    pub y: usize,
+    /// Index into the variants of the matcher
+    pub variant: usize,
+}
+
+/// Finds a pattern in all of its orientations
+pub struct Matcher {
+    variants: Vec<Pattern>,
+}
+
+impl Matcher {
+    /// Matches all rotations and reflections of the pattern
+    pub fn new(pattern: &Pattern) -> Self {
+        Matcher {
+            variants: pattern.variants(),
+        }
+    }
+
+    /// Only matches the pattern as it is
+    pub fn exact(pattern: &Pattern) -> Self {
+        Matcher {
+            variants: vec![pattern.clone()],
+        }
+    }
+
+    pub fn variants(&self) -> &[Pattern] {
+        &self.variants
+    }
+
+    fn matches_at(pattern: &Pattern, grid: &[Vec<char>], x: usize, y: usize) -> bool {
+        (0..pattern.height).all(|dy| {
+            (0..pattern.width).all(|dx| match pattern.get(dx, dy) {
+                Some(c) => grid[y + dy].get(x + dx) == Some(&c),
+                None => x + dx < grid[y + dy].len(),
+            })
+        })
+    }
+
+    /// All matches, row by row. If different variants match exactly the same cells,
+    /// which happens if wildcards make them overlap, only the first one is returned.
+    pub fn find(&self, grid: &[Vec<char>]) -> Vec<PatternMatch> {
+        let mut matches = vec![];
+        let mut seen = BTreeSet::new();
+        let width = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+
+        for y in 0..grid.len() {
+            for x in 0..width {
+                for (variant, pattern) in self.variants.iter().enumerate() {
+                    if y + pattern.height > grid.len() || !Self::matches_at(pattern, grid, x, y) {
+                        continue;
+                    }
+                    let cells: Vec<(usize, usize)> = (0..pattern.height)
+                        .flat_map(|dy| (0..pattern.width).map(move |dx| (dx, dy)))
+                        .filter(|&(dx, dy)| pattern.get(dx, dy).is_some())
+                        .map(|(dx, dy)| (x + dx, y + dy))
+                        .collect();
+                    if seen.insert(cells) {
+                        matches.push(PatternMatch { x, y, variant });
+                    }
+                }
+            }
+        }
+        matches
+    }
+
+    pub fn count(&self, grid: &[Vec<char>]) -> usize {
+        self.find(grid).len()
+    }
+}
+
+#[cfg(test)]
+fn grid(text: &str) -> Vec<Vec<char>> {
+    text.lines().map(|l| l.chars().collect()).collect()
+}
+
+#[test]
+fn test_variants() {
+    let x_mas = Pattern::parse("M.S / .A. / M.S").unwrap();
+    assert_eq!(format!("{:?}", x_mas), "M.S\n.A.\nM.S\n");
+    assert_eq!(format!("{:?}", x_mas.rotated()), "M.M\n.A.\nS.S\n");
+    // Reflections of the X are rotations as well
+    assert_eq!(x_mas.variants().len(), 4);
+
+    let l = Pattern::parse("A.\nBC\n").unwrap();
+    assert_eq!(l.variants().len(), 8);
+    assert_eq!(format!("{:?}", l.rotated()), "BA\nC.\n");
+    assert_eq!(
+        Pattern::parse("AAA").unwrap().variants(),
+        vec![
+            Pattern::parse("AAA").unwrap(),
+            Pattern::parse("A/A/A").unwrap()
+        ]
+    );
+
+    assert!(Pattern::parse("").is_err());
+    assert!(Pattern::parse("AB/C").is_err());
+}
+
+#[test]
+fn test_find() {
+    let matcher = Matcher::new(&Pattern::parse("M.S/.A./M.S").unwrap());
+    let matches = matcher.find(&grid("M.S.\n.A..\nM.S.\n.A..\nM.S."));
+    assert_eq!(
+        matches,
+        vec![
+            PatternMatch {
+                x: 0,
+                y: 0,
+                variant: 0
+            },
+            PatternMatch {
+                x: 0,
+                y: 2,
+                variant: 0
+            },
+        ]
+    );
+
+    let diagonal = Pattern::parse("A./.A").unwrap();
+    let square = grid("AA\nAA");
+    assert_eq!(Matcher::new(&diagonal).count(&square), 2);
+    assert_eq!(Matcher::exact(&diagonal).count(&square), 1);
+
+    // All four variants match only the `A` in the middle
+    let corner = Matcher::new(&Pattern::parse("A./..").unwrap());
+    assert_eq!(corner.variants().len(), 4);
+    assert_eq!(corner.count(&grid("...\n.A.\n...")), 1);
+}
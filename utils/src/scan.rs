@@ -0,0 +1,216 @@
+use std::io::{self, Read};
+
+use memchr::memmem::Finder;
+
+const DEFAULT_CHUNK_SIZE: usize = 1 << 20;
+
+/// A match found by a [`StreamScanner`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match<T> {
+    /// Byte offset of the match in the whole stream
+    pub offset: u64,
+    pub len: usize,
+    pub value: T,
+}
+
+struct Pattern {
+    finder: Finder<'static>,
+    /// Next occurrence in the buffer at or after the scan position, if already searched
+    next: Option<usize>,
+    searched: bool,
+}
+
+/// Finds matches in a stream without looking at every byte: the input is searched for
+/// the prefixes a match has to start with, and the parser is only run at those offsets.
+///
+/// Matches can be at most `window` bytes long. The stream is read in chunks, and the
+/// parser is only run once at least `window` bytes are buffered after a candidate, so
+/// matches across chunk boundaries are found as well.
+///
+/// ```
+/// use aoc_utils::scan::StreamScanner;
+///
+/// let input = "xxab12yyab3ab".as_bytes();
+/// let mut scanner = StreamScanner::new(input, &["ab"], 4).with_chunk_size(3);
+/// let parse = |s: &str| {
+///     let digits = s[2..].bytes().take_while(u8::is_ascii_digit).count();
+///     (digits > 0).then(|| (s[2..2 + digits].to_string(), 2 + digits))
+/// };
+/// let mut found = vec![];
+/// while let Some(m) = scanner.next_match(parse).unwrap() {
+///     found.push((m.offset, m.value));
+/// }
+/// assert_eq!(found, vec![(2, "12".to_string()), (8, "3".to_string())]);
+/// ```
+pub struct StreamScanner<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    /// Offset of `buffer[0]` in the stream
+    offset: u64,
+    /// Scan position in the buffer
+    pos: usize,
+    eof: bool,
+    chunk_size: usize,
+    window: usize,
+    patterns: Vec<Pattern>,
+}
+
+impl<R: Read> StreamScanner<R> {
+    pub fn new(reader: R, prefixes: &[&str], window: usize) -> Self {
+        assert!(!prefixes.is_empty(), "At least one prefix is needed");
+        assert!(
+            prefixes.iter().all(|p| !p.is_empty() && p.len() <= window),
+            "Prefixes must be non-empty and fit into the window"
+        );
+        StreamScanner {
+            reader,
+            buffer: vec![],
+            offset: 0,
+            pos: 0,
+            eof: false,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            window,
+            patterns: prefixes
+                .iter()
+                .map(|p| Pattern {
+                    finder: Finder::new(p.as_bytes()).into_owned(),
+                    next: None,
+                    searched: false,
+                })
+                .collect(),
+        }
+    }
+
+    /// How many bytes are read at once
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0);
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Drops the scanned part of the buffer and reads the next chunk
+    fn refill(&mut self, keep_from: usize) -> io::Result<()> {
+        self.buffer.drain(..keep_from);
+        self.offset += keep_from as u64;
+        self.pos -= keep_from;
+        for pattern in &mut self.patterns {
+            pattern.next = pattern
+                .next
+                .filter(|&n| n >= keep_from)
+                .map(|n| n - keep_from);
+            // The prefix may be in the new data
+            pattern.searched &= pattern.next.is_some();
+        }
+
+        let len = self.buffer.len();
+        self.buffer.resize(len + self.chunk_size, 0);
+        let read = loop {
+            match self.reader.read(&mut self.buffer[len..]) {
+                Ok(read) => break read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.buffer.truncate(len);
+                    return Err(e);
+                }
+            }
+        };
+        self.buffer.truncate(len + read);
+        self.eof = read == 0;
+        Ok(())
+    }
+
+    /// The closest occurrence of any prefix at or after the scan position
+    fn candidate(&mut self) -> Option<usize> {
+        let (buffer, pos) = (&self.buffer, self.pos);
+        self.patterns
+            .iter_mut()
+            .filter_map(|pattern| {
+                if !pattern.searched || pattern.next.is_some_and(|n| n < pos) {
+                    pattern.next = pattern.finder.find(&buffer[pos..]).map(|n| n + pos);
+                    pattern.searched = true;
+                }
+                pattern.next
+            })
+            .min()
+    }
+
+    /// Finds the next match. `parse` gets the input starting at a candidate prefix, truncated
+    /// to the window, and returns the parsed value and its length if the candidate matches.
+    /// Scanning continues after a match, or after the candidate's first byte otherwise.
+    pub fn next_match<T>(
+        &mut self,
+        mut parse: impl FnMut(&str) -> Option<(T, usize)>,
+    ) -> io::Result<Option<Match<T>>> {
+        loop {
+            match self.candidate() {
+                Some(start) if self.eof || start + self.window <= self.buffer.len() => {
+                    let end = (start + self.window).min(self.buffer.len());
+                    // Matches are ASCII, so a multi-byte character cut off by the window
+                    // ends the input just like any other non-matching byte
+                    let text = match std::str::from_utf8(&self.buffer[start..end]) {
+                        Ok(text) => text,
+                        Err(e) => std::str::from_utf8(&self.buffer[start..start + e.valid_up_to()])
+                            .unwrap(),
+                    };
+                    match parse(text) {
+                        Some((value, len)) => {
+                            self.pos = start + len.max(1);
+                            return Ok(Some(Match {
+                                offset: self.offset + start as u64,
+                                len,
+                                value,
+                            }));
+                        }
+                        None => self.pos = start + 1,
+                    }
+                }
+                Some(start) => {
+                    // Not enough input buffered to parse the candidate
+                    self.pos = start;
+                    self.refill(start)?;
+                }
+                None if self.eof => return Ok(None),
+                None => {
+                    // A prefix may start in the unsearched tail of the buffer
+                    let tail = self.window - 1;
+                    self.pos = self.pos.max(self.buffer.len().saturating_sub(tail));
+                    self.refill(self.pos)?;
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_matches_across_chunks() {
+    let input = "mul(1,2)xmul(33,4)do()mu".repeat(5);
+    let parse = |s: &str| {
+        let end = s.find(')')?;
+        Some((s[..=end].to_string(), end + 1))
+    };
+    for chunk_size in [1, 2, 3, 7, 1000] {
+        let mut scanner =
+            StreamScanner::new(input.as_bytes(), &["mul(", "do("], 10).with_chunk_size(chunk_size);
+        let mut found = vec![];
+        while let Some(m) = scanner.next_match(parse).unwrap() {
+            assert_eq!(&input[m.offset as usize..][..m.len], m.value);
+            found.push(m.value);
+        }
+        assert_eq!(found.len(), 15);
+        assert_eq!(&found[..3], &["mul(1,2)", "mul(33,4)", "do()"]);
+    }
+}
+
+#[test]
+fn test_utf8_at_window_end() {
+    let input = "ab1ä ab2".as_bytes();
+    let mut scanner = StreamScanner::new(input, &["ab"], 4).with_chunk_size(1);
+    let mut found = vec![];
+    while let Some(m) = scanner
+        .next_match(|s| Some((s.to_string(), s.len())))
+        .unwrap()
+    {
+        found.push(m.value);
+    }
+    assert_eq!(found, vec!["ab1", "ab2"]);
+}